@@ -1,4 +1,5 @@
 use std::cmp::min;
+use std::collections::{BTreeMap, BTreeSet};
 
 use ndarray::{Array2, Ix2};
 use sorted_vec::SortedSet;
@@ -6,17 +7,43 @@ use sorted_vec::SortedSet;
 use crate::container::{
     contents_positions, Component, Container, ContainerLUT, ContainerTrait, Contents,
 };
+use crate::movement::{Change, Move};
 use crate::obstacle::Obstacle;
 use crate::ordered::OrdIx2;
 use crate::particle::{Particle, ParticleTrait};
 use crate::property::Direction;
 
-#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub use self::level::{ParseLevelError, ParseLevelErrorKind};
+pub use self::packed::{PackedBoard, PackedLevel};
+#[cfg(feature = "serde")]
+pub use self::schema::{BoardSchemaError, SCHEMA_VERSION};
+pub use self::symmetry::Symmetry;
+
+mod level;
+mod packed;
+#[cfg(feature = "serde")]
+mod schema;
+mod symmetry;
+mod zobrist;
+
+/// Remaining global moves before the particle at each position decays.
+pub type Lifetimes = BTreeMap<OrdIx2, u32>;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "schema::BoardRepr", try_from = "schema::BoardRepr")
+)]
 pub struct Board {
+    /// Kept up to date by every write, see the `zobrist` module. First so that
+    /// comparing different boards usually stops here.
+    zobrist: u64,
     width: usize,
     height: usize,
     goals: SortedSet<OrdIx2>,
     container_lut: ContainerLUT,
+    lifetimes: Lifetimes,
     particles: Array2<Particle>,
     obstacles: Array2<Obstacle>,
 }
@@ -30,6 +57,19 @@ impl Board {
         &self.obstacles
     }
 
+    pub fn lifetimes(&self) -> &Lifetimes {
+        &self.lifetimes
+    }
+
+    /// The container each bound component is directly held in.
+    pub fn containers(&self) -> &ContainerLUT {
+        &self.container_lut
+    }
+
+    pub fn goals(&self) -> &SortedSet<OrdIx2> {
+        &self.goals
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -40,10 +80,12 @@ impl Board {
 
     pub fn new(width: usize, height: usize, goals: SortedSet<OrdIx2>) -> Self {
         Self {
+            zobrist: 0,
             width,
             height,
             goals,
             container_lut: ContainerLUT::new(),
+            lifetimes: Lifetimes::new(),
             particles: Array2::default([width, height]),
             obstacles: Array2::default([width, height]),
         }
@@ -55,15 +97,138 @@ impl Board {
             .all(|&i| !matches!(self.particles.get(*i).unwrap(), Particle::Empty(_)))
     }
 
-    pub fn all_moves(&self) -> Vec<Board> {
+    /// Moves every top-level container as a unit and every particle that is
+    /// not bound in a container on its own. Each move is one global move, so
+    /// every lifetime is counted down as part of it.
+    pub fn all_moves(&self) -> Vec<Move> {
+        let containers: BTreeSet<&Container> = self
+            .container_lut
+            .keys()
+            .filter_map(|component| self.top_container(component))
+            .collect();
+
         self.particles
             .indexed_iter()
             .filter(|&(_pos, particle)| !matches!(particle, Particle::Empty(_)))
+            .filter(|&(pos, _particle)| {
+                !self
+                    .container_lut
+                    .contains_key(&Component::Particle(OrdIx2::from(Ix2(pos.0, pos.1))))
+            })
             .flat_map(|(pos, particle)| particle.all_moves(self, Ix2(pos.0, pos.1)))
+            .chain(
+                containers
+                    .into_iter()
+                    .flat_map(|container| container.all_moves(self)),
+            )
+            .map(|m| m.tick(self))
             .collect()
     }
 
+    /// The board after each of [`Board::all_moves`].
+    pub fn next_boards(&self) -> Vec<Board> {
+        self.all_moves().iter().map(|m| self.applied(m)).collect()
+    }
+
+    /// Makes a move generated for this board.
+    pub fn apply(&mut self, m: &Move) {
+        for change in m.changes() {
+            self.replace(change, false);
+        }
+    }
+
+    /// Takes back a move made with [`Board::apply`].
+    pub fn undo(&mut self, m: &Move) {
+        for change in m.changes().iter().rev() {
+            self.replace(change, true);
+        }
+    }
+
+    pub fn applied(&self, m: &Move) -> Board {
+        let mut board = self.clone();
+        board.apply(m);
+        board
+    }
+
+    /// The first of [`Board::all_moves`] that turns this board into `next`.
+    pub fn move_to(&self, next: &Board) -> Option<Move> {
+        let mut board = self.clone();
+        self.all_moves().into_iter().find(|m| {
+            board.apply(m);
+            let found = &board == next;
+            board.undo(m);
+            found
+        })
+    }
+
+    /// Sets the part of the board touched by `change` to its value after the
+    /// change, or before it if `undo` is set.
+    fn replace(&mut self, change: &Change, undo: bool) {
+        match change {
+            Change::Particle { pos, before, after } => {
+                self.put_particle(**pos, if undo { *before } else { *after });
+            }
+            Change::Lifetime { pos, before, after } => {
+                self.put_lifetime(*pos, if undo { *before } else { *after });
+            }
+            Change::Obstacle { pos, before, after } => {
+                self.put_obstacle(**pos, if undo { *before } else { *after });
+            }
+            Change::Container { before, after } => {
+                let (removed, added) = if undo {
+                    (after, before)
+                } else {
+                    (before, after)
+                };
+                if let Some(container) = removed {
+                    self.remove_container(container);
+                }
+                if let Some(container) = added {
+                    self.add_container(container.clone());
+                }
+            }
+        }
+    }
+
+    /// Counts every lifetime down by one global move. Particles whose lifetime
+    /// runs out decay into their decay product, which starts its own
+    /// lifetime, or stay as they are if they have none.
+    pub fn tick(&mut self) {
+        let positions: Vec<OrdIx2> = self.lifetimes.keys().copied().collect();
+        for pos in positions {
+            let lifetime = self.lifetimes[&pos].saturating_sub(1);
+            self.put_lifetime(pos, Some(lifetime).filter(|&lifetime| lifetime > 0));
+            if lifetime == 0 {
+                if let Some(product) = self.particles[*pos].decay() {
+                    self.put_particle(*pos, product);
+                    self.put_lifetime(
+                        pos,
+                        Some(product.lifetime()).filter(|&lifetime| lifetime > 0),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn lifetime(&self, pos: Ix2) -> Option<u32> {
+        self.lifetimes.get(&OrdIx2::from(pos)).copied()
+    }
+
+    /// Makes the particle at `pos` decay after `lifetime` global moves, or
+    /// stable again if `lifetime` is zero.
+    pub fn set_lifetime(&mut self, pos: Ix2, lifetime: u32) {
+        self.put_lifetime(pos.into(), Some(lifetime).filter(|&lifetime| lifetime > 0));
+    }
+
     pub fn annihilate(&mut self, pos: Ix2, strength: usize) {
+        for pos in self.annihilation_area(pos, strength) {
+            self.remove_obstacle(pos);
+        }
+    }
+
+    /// Cells within `strength` steps of `pos`, whose obstacles an annihilation
+    /// there clears.
+    pub fn annihilation_area(&self, pos: Ix2, strength: usize) -> Vec<Ix2> {
         let x = pos[0];
         let y = pos[1];
 
@@ -72,54 +237,60 @@ impl Board {
         let max_x = min(x + strength + 1, self.width);
         let max_y = min(y + strength + 1, self.height);
 
+        let mut area = Vec::new();
         for j in min_y..max_y {
             for i in min_x..max_x {
                 if i.abs_diff(x) + j.abs_diff(y) <= strength {
-                    self.remove_obstacle(Ix2(i, j));
+                    area.push(Ix2(i, j));
                 }
             }
         }
+
+        area
     }
 
     pub fn move_particle(&mut self, from_pos: Ix2, to_pos: Ix2) -> Option<Particle> {
         if from_pos == to_pos {
             return None;
         }
-        self.particles.swap(from_pos, to_pos);
+        let moving = self.put_particle(from_pos, Particle::default());
+        let replaced = self.put_particle(to_pos, moving);
+        let lifetime = self.put_lifetime(from_pos.into(), None);
+        self.put_lifetime(to_pos.into(), lifetime);
 
-        Some(std::mem::take(
-            self.particles
-                .get_mut(from_pos)
-                .expect("out of index should had be panicked by swap"),
-        ))
+        Some(replaced)
     }
 
     pub fn add_particle(&mut self, particle: impl Into<Particle>, pos: Ix2) {
-        self.particles[pos] = particle.into();
+        self.put_particle(pos, particle.into());
+        self.put_lifetime(pos.into(), None);
     }
 
     pub fn remove_particle(&mut self, pos: Ix2) -> Option<Particle> {
-        Some(std::mem::take(&mut *self.particles.get_mut(pos)?))
+        self.particles.get(pos)?;
+        let particle = self.put_particle(pos, Particle::default());
+        self.put_lifetime(pos.into(), None);
+        Some(particle)
     }
 
     pub fn add_obstacle(&mut self, obstacle: impl Into<Obstacle>, pos: Ix2) {
-        self.obstacles[pos] = obstacle.into();
+        self.put_obstacle(pos, obstacle.into());
     }
 
     pub fn remove_obstacle(&mut self, pos: Ix2) -> Option<Obstacle> {
-        Some(std::mem::take(&mut *self.obstacles.get_mut(pos)?))
+        self.obstacles.get(pos)?;
+        Some(self.put_obstacle(pos, Obstacle::default()))
     }
 
     pub fn add_container(&mut self, container: Container) {
         for component in container.contents().iter() {
-            self.container_lut
-                .insert(component.clone(), container.clone());
+            self.put_container(component, Some(&container));
         }
     }
 
     pub fn remove_container(&mut self, contents: &Container) {
         for component in contents.contents().iter() {
-            self.container_lut.remove(component);
+            self.put_container(component, None);
         }
     }
 
@@ -213,6 +384,9 @@ impl Board {
         0
     }
 
+    /// Sums the charge of the nearest charged component seen from each cell
+    /// of `contents`, counting every component once and skipping the
+    /// container that holds `contents` itself.
     pub fn find_charge_many(&self, contents: &Contents, direction: Direction) -> i32 {
         let mut visited = SortedSet::new();
         let mut total_charge = 0;
@@ -222,6 +396,9 @@ impl Board {
                 Ix2::from(**pos),
                 direction,
                 |c| {
+                    if c.contents() == contents {
+                        return None;
+                    }
                     let charge = c.charge();
                     if charge != 0 {
                         return Some(charge);
@@ -239,7 +416,6 @@ impl Board {
                 if let (_, None) = visited.push(component) {
                     total_charge += charge;
                 };
-                break;
             };
         }
 
@@ -325,6 +501,9 @@ impl Board {
                     Particle::Muon(_) => "𝜇",
                     Particle::Tau(_) => "𝜏",
                     Particle::Neutron(_) => "n",
+                    Particle::Proton(_) => "p",
+                    Particle::UpQuark(_) => "u",
+                    Particle::DownQuark(_) => "d",
                 })
                 .collect();
             println!("║ {} ║", x.join(" │ "));
@@ -338,7 +517,9 @@ impl Board {
 
 #[cfg(test)]
 mod tests {
-    use crate::particle::{Electron, Empty};
+    use crate::container::NucleusParticle;
+    use crate::particle::{Electron, Empty, Muon, Neutron, Proton, Tau};
+    use crate::property::Antiness;
 
     use super::*;
 
@@ -363,4 +544,83 @@ mod tests {
         board.remove_particle(Ix2(1, 0));
         assert_eq!(board.particles().get([1, 0]), Some(&Empty.into()));
     }
+
+    #[test]
+    fn board_all_moves_keeps_nucleus_together() {
+        let mut board = Board::new(5, 5, vec![].into());
+        board.add_particle(Proton::default(), Ix2(1, 1));
+        board.add_particle(Neutron::default(), Ix2(2, 1));
+        board.add_particle(Electron::default(), Ix2(4, 4));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(1, 1).into()].into(),
+                vec![Ix2(2, 1).into()].into(),
+            )
+            .into(),
+        );
+
+        let moves = board.next_boards();
+        assert_eq!(moves.len(), 2 + 4);
+        for new_board in &moves {
+            let bound: Vec<_> = new_board
+                .container_lut
+                .keys()
+                .flat_map(|component| component.all_positions())
+                .collect();
+            assert_eq!(bound.len(), 2);
+            assert_eq!(bound[0][1], bound[1][1]);
+            assert_eq!(bound[0][0] + 1, bound[1][0]);
+            assert_eq!(
+                new_board.particles().get(*bound[0]),
+                Some(&Proton::default().into())
+            );
+            assert_eq!(
+                new_board.particles().get(*bound[1]),
+                Some(&Neutron::default().into())
+            );
+        }
+    }
+
+    #[test]
+    fn board_decay() {
+        let mut board = Board::new(3, 3, vec![].into());
+        board.add_particle(Tau::default(), Ix2(0, 0));
+        board.set_lifetime(Ix2(0, 0), 1);
+        board.add_particle(Muon::default(), Ix2(2, 2));
+        board.set_lifetime(Ix2(2, 2), 2);
+
+        for new_board in board.next_boards() {
+            let tau_decayed = new_board
+                .particles()
+                .iter()
+                .filter(|&&p| p == Muon::default().into())
+                .count();
+            assert_eq!(tau_decayed, 2);
+            let mut lifetimes: Vec<u32> = new_board.lifetimes().values().copied().collect();
+            lifetimes.sort();
+            assert_eq!(lifetimes, vec![1, Muon::default().lifetime()]);
+        }
+
+        let mut moved = board.clone();
+        moved.move_particle(Ix2(2, 2), Ix2(2, 0));
+        assert_eq!(moved.lifetime(Ix2(2, 0)), Some(2));
+        assert_ne!(moved.next_boards(), board.next_boards());
+        moved.tick();
+        moved.tick();
+        assert_eq!(
+            moved.particles().get([2, 0]),
+            Some(&Electron::default().into())
+        );
+        assert_eq!(moved.lifetimes().len(), 1);
+
+        for _ in 0..Muon::default().lifetime() - 1 {
+            moved.tick();
+        }
+        assert_eq!(
+            moved.particles().get([0, 0]),
+            Some(&Electron::default().into())
+        );
+        assert!(moved.lifetimes().is_empty());
+    }
 }
@@ -0,0 +1,720 @@
+//! Plain-text level format for [`Board`].
+//!
+//! A level is a grid of whitespace separated cells, one line per row, followed
+//! by optional nucleus declarations. Lines starting with `;` are comments and
+//! blank lines are ignored.
+//!
+//! ```text
+//! ; two electrons, a neutron and a deuteron
+//! e  .  #  n[n1]
+//! .  E  o  p[p1]
+//! .* .  .  .*
+//! nucleus 1 ordinary
+//! ```
+//!
+//! Each cell is written as `<particle><obstacle><goal><membership>`, every part
+//! being optional, and `.` on its own for a cell with nothing in it.
+//!
+//! - particle: `e` electron, `m` (or `𝜇`) muon, `t` (or `𝜏`) tau, `n` neutron,
+//!   `p` proton, `u` up quark, `d` down quark, upper case for the
+//!   antiparticle, `.` for no particle
+//! - colour, quarks only: `w` white, `r` `g` `b` `y` `c` `m` for red, green,
+//!   blue, yellow, cyan and magenta, upper case for the anticolour
+//! - lifetime: `~<moves>` for a particle that decays after that many moves
+//! - obstacle: `#` block, `o` hole
+//! - goal: `*`
+//! - membership: `[p<label>]` or `[n<label>]` marks the cell as a proton or a
+//!   neutron of the nucleus `<label>`, `[b<label>]` as both, `[q<label>]` as a
+//!   quark of the hadron `<label>`
+//!
+//! A nucleus declaration is `nucleus <label> [ordinary|anti]`. Nuclei that are
+//! used in the grid but never declared are ordinary. Hadrons need no
+//! declaration but must be colour neutral.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use ndarray::Ix2;
+use sorted_vec::SortedSet;
+
+use crate::container::{ConfinementError, Container, Hadron, NucleusParticle};
+use crate::obstacle::{block::Block, hole::Hole, Obstacle};
+use crate::ordered::OrdIx2;
+use crate::particle::{DownQuark, Electron, Muon, Neutron, Particle, Proton, Tau, UpQuark};
+use crate::property::{AntiTrait, Antiness, Colour, ColourTrait};
+
+use super::Board;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseLevelErrorKind {
+    Empty,
+    UnexpectedChar(char),
+    MissingColour,
+    InvalidLifetime,
+    UnterminatedMembership,
+    RaggedRow { expected: usize, found: usize },
+    InvalidDeclaration,
+    DuplicateNucleus(String),
+    EmptyNucleus(String),
+    MixedMembership(String),
+    Confinement(String, ConfinementError),
+}
+
+impl Display for ParseLevelErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "level has no rows"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character `{c}`"),
+            Self::MissingColour => write!(f, "quark is missing its colour"),
+            Self::InvalidLifetime => write!(f, "lifetime must be a positive number"),
+            Self::UnterminatedMembership => write!(f, "membership is missing a closing `]`"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "row has {found} cells, expected {expected}")
+            }
+            Self::InvalidDeclaration => {
+                write!(f, "expected `nucleus <label> [ordinary|anti]`")
+            }
+            Self::DuplicateNucleus(label) => write!(f, "nucleus `{label}` is declared twice"),
+            Self::EmptyNucleus(label) => write!(f, "nucleus `{label}` has no members"),
+            Self::MixedMembership(label) => {
+                write!(f, "`{label}` is used for both a nucleus and a hadron")
+            }
+            Self::Confinement(label, error) => write!(f, "hadron `{label}`: {error}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseLevelError {
+    line: usize,
+    column: usize,
+    kind: ParseLevelErrorKind,
+}
+
+impl ParseLevelError {
+    fn new(line: usize, column: usize, kind: ParseLevelErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// One-based line of the error.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// One-based column of the error, counted in characters.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn kind(&self) -> &ParseLevelErrorKind {
+        &self.kind
+    }
+}
+
+impl Error for ParseLevelError {}
+
+impl Display for ParseLevelError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Proton,
+    Neutron,
+    /// Listed among both the protons and the neutrons of a nucleus.
+    Both,
+    Quark,
+}
+
+struct Cell {
+    at: (usize, usize),
+    particle: Particle,
+    lifetime: u32,
+    obstacle: Obstacle,
+    goal: bool,
+    membership: Option<(Role, String)>,
+}
+
+#[derive(Default)]
+struct ContainerEntry {
+    antiness: Antiness,
+    declared_at: Option<(usize, usize)>,
+    first_member_at: Option<(usize, usize)>,
+    protons: Vec<OrdIx2>,
+    neutrons: Vec<OrdIx2>,
+    quarks: Vec<OrdIx2>,
+}
+
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    let mut start = None;
+    let mut result = Vec::new();
+    for (byte, c) in line.char_indices() {
+        column += 1;
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, byte)),
+            (true, Some((token_column, token_byte))) => {
+                result.push((token_column, &line[token_byte..byte]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((token_column, token_byte)) = start {
+        result.push((token_column, &line[token_byte..]));
+    }
+
+    result.into_iter()
+}
+
+fn parse_particle(c: char) -> Option<Particle> {
+    let anti = match c.is_uppercase() {
+        true => Antiness::Anti,
+        false => Antiness::Ordinary,
+    };
+    Some(match c.to_ascii_lowercase() {
+        '.' => Particle::default(),
+        'e' => Electron::new(anti).into(),
+        'm' | '𝜇' => Muon::new(anti).into(),
+        't' | '𝜏' => Tau::new(anti).into(),
+        'n' => Neutron::new(anti).into(),
+        'p' => Proton::new(anti).into(),
+        'u' => UpQuark::new(anti, Colour::White).into(),
+        'd' => DownQuark::new(anti, Colour::White).into(),
+        _ => return None,
+    })
+}
+
+fn parse_colour(c: char) -> Option<Colour> {
+    Some(match c {
+        'w' => Colour::White,
+        'r' => Colour::Red,
+        'g' => Colour::Green,
+        'b' => Colour::Blue,
+        'y' => Colour::Yellow,
+        'c' => Colour::Cyan,
+        'm' => Colour::Magenta,
+        'R' => Colour::AntiRed,
+        'G' => Colour::AntiGreen,
+        'B' => Colour::AntiBlue,
+        'Y' => Colour::AntiYellow,
+        'C' => Colour::AntiCyan,
+        'M' => Colour::AntiMagenta,
+        _ => return None,
+    })
+}
+
+fn colour_symbol(colour: Colour) -> char {
+    match colour {
+        Colour::White => 'w',
+        Colour::Red => 'r',
+        Colour::Green => 'g',
+        Colour::Blue => 'b',
+        Colour::Yellow => 'y',
+        Colour::Cyan => 'c',
+        Colour::Magenta => 'm',
+        Colour::AntiRed => 'R',
+        Colour::AntiGreen => 'G',
+        Colour::AntiBlue => 'B',
+        Colour::AntiYellow => 'Y',
+        Colour::AntiCyan => 'C',
+        Colour::AntiMagenta => 'M',
+    }
+}
+
+fn particle_symbol(particle: &Particle) -> String {
+    let (symbol, anti, colour) = match particle {
+        Particle::Empty(_) => return String::new(),
+        Particle::Electron(p) => ('e', p.anti(), None),
+        Particle::Muon(p) => ('m', p.anti(), None),
+        Particle::Tau(p) => ('t', p.anti(), None),
+        Particle::Neutron(p) => ('n', p.anti(), None),
+        Particle::Proton(p) => ('p', p.anti(), None),
+        Particle::UpQuark(p) => ('u', p.anti(), Some(p.colour())),
+        Particle::DownQuark(p) => ('d', p.anti(), Some(p.colour())),
+    };
+
+    let mut result = String::from(match anti {
+        Antiness::Ordinary => symbol,
+        Antiness::Anti => symbol.to_ascii_uppercase(),
+    });
+    result.extend(colour.map(colour_symbol));
+    result
+}
+
+fn obstacle_symbol(obstacle: &Obstacle) -> Option<char> {
+    match obstacle {
+        Obstacle::Empty(_) => None,
+        Obstacle::Block(_) => Some('#'),
+        Obstacle::Hole(_) => Some('o'),
+    }
+}
+
+fn parse_cell(line: usize, column: usize, token: &str) -> Result<Cell, ParseLevelError> {
+    let mut chars = token.chars().enumerate().peekable();
+    let error = |offset: usize, kind| ParseLevelError::new(line, column + offset, kind);
+
+    let mut particle = Particle::default();
+    if let Some(p) = chars.peek().and_then(|&(_, c)| parse_particle(c)) {
+        particle = p;
+        chars.next();
+    }
+    if let Particle::UpQuark(_) | Particle::DownQuark(_) = particle {
+        let colour = match chars.next() {
+            Some((offset, c)) => {
+                parse_colour(c).ok_or(error(offset, ParseLevelErrorKind::UnexpectedChar(c)))?
+            }
+            None => {
+                return Err(error(
+                    token.chars().count(),
+                    ParseLevelErrorKind::MissingColour,
+                ))
+            }
+        };
+        particle = match particle {
+            Particle::UpQuark(q) => UpQuark::new(q.anti(), colour).into(),
+            Particle::DownQuark(q) => DownQuark::new(q.anti(), colour).into(),
+            _ => unreachable!(),
+        };
+    }
+
+    let mut lifetime = 0;
+    if let Some(&(start, '~')) = chars.peek() {
+        if matches!(particle, Particle::Empty(_)) {
+            return Err(error(start, ParseLevelErrorKind::UnexpectedChar('~')));
+        }
+        chars.next();
+        let mut digits = String::new();
+        while let Some(&(_, c)) = chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            chars.next();
+        }
+        lifetime = digits
+            .parse()
+            .ok()
+            .filter(|&lifetime| lifetime > 0)
+            .ok_or(error(start, ParseLevelErrorKind::InvalidLifetime))?;
+    }
+
+    let mut obstacle = Obstacle::default();
+    match chars.peek() {
+        Some((_, '#')) => obstacle = Block.into(),
+        Some((_, 'o')) => obstacle = Hole.into(),
+        _ => (),
+    }
+    if !matches!(obstacle, Obstacle::Empty(_)) {
+        chars.next();
+    }
+
+    let goal = matches!(chars.peek(), Some((_, '*')));
+    if goal {
+        chars.next();
+    }
+
+    let mut membership = None;
+    if let Some(&(start, '[')) = chars.peek() {
+        chars.next();
+        let role = match chars.next() {
+            Some((_, 'p')) => Role::Proton,
+            Some((_, 'n')) => Role::Neutron,
+            Some((_, 'b')) => Role::Both,
+            Some((_, 'q')) => Role::Quark,
+            Some((offset, c)) => return Err(error(offset, ParseLevelErrorKind::UnexpectedChar(c))),
+            None => return Err(error(start, ParseLevelErrorKind::UnterminatedMembership)),
+        };
+        let mut label = String::new();
+        loop {
+            match chars.next() {
+                Some((_, ']')) if !label.is_empty() => break,
+                Some((_, c)) if c.is_ascii_alphanumeric() => label.push(c),
+                Some((offset, c)) => {
+                    return Err(error(offset, ParseLevelErrorKind::UnexpectedChar(c)))
+                }
+                None => return Err(error(start, ParseLevelErrorKind::UnterminatedMembership)),
+            }
+        }
+        membership = Some((role, label));
+    }
+
+    if let Some((offset, c)) = chars.next() {
+        return Err(error(offset, ParseLevelErrorKind::UnexpectedChar(c)));
+    }
+
+    Ok(Cell {
+        at: (line, column),
+        particle,
+        lifetime,
+        obstacle,
+        goal,
+        membership,
+    })
+}
+
+fn parse_declaration<'a>(
+    line: usize,
+    mut tokens: impl Iterator<Item = (usize, &'a str)>,
+) -> Result<(usize, String, Antiness), ParseLevelError> {
+    let invalid =
+        |column| ParseLevelError::new(line, column, ParseLevelErrorKind::InvalidDeclaration);
+
+    let (column, label) = tokens.next().ok_or_else(|| invalid(1))?;
+    if let Some((offset, c)) = label
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_alphanumeric())
+    {
+        return Err(ParseLevelError::new(
+            line,
+            column + offset,
+            ParseLevelErrorKind::UnexpectedChar(c),
+        ));
+    }
+    let antiness = match tokens.next() {
+        None | Some((_, "ordinary")) => Antiness::Ordinary,
+        Some((_, "anti")) => Antiness::Anti,
+        Some((column, _)) => return Err(invalid(column)),
+    };
+    if let Some((column, _)) = tokens.next() {
+        return Err(invalid(column));
+    }
+
+    Ok((column, label.to_string(), antiness))
+}
+
+impl FromStr for Board {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows: Vec<Vec<Cell>> = Vec::new();
+        let mut containers: BTreeMap<String, ContainerEntry> = BTreeMap::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let mut line_tokens = tokens(line).peekable();
+            match line_tokens.peek() {
+                None => continue,
+                Some((_, token)) if token.starts_with(';') => continue,
+                Some((_, "nucleus")) => {
+                    line_tokens.next();
+                    let (column, label, antiness) = parse_declaration(line_number, line_tokens)?;
+                    let entry = containers.entry(label.clone()).or_default();
+                    if entry.declared_at.is_some() {
+                        return Err(ParseLevelError::new(
+                            line_number,
+                            column,
+                            ParseLevelErrorKind::DuplicateNucleus(label),
+                        ));
+                    }
+                    entry.declared_at = Some((line_number, column));
+                    entry.antiness = antiness;
+                }
+                Some(_) => {
+                    let row = line_tokens
+                        .map(|(column, token)| parse_cell(line_number, column, token))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if let Some(first) = rows.first() {
+                        if first.len() != row.len() {
+                            return Err(ParseLevelError::new(
+                                line_number,
+                                1,
+                                ParseLevelErrorKind::RaggedRow {
+                                    expected: first.len(),
+                                    found: row.len(),
+                                },
+                            ));
+                        }
+                    }
+                    rows.push(row);
+                }
+            }
+        }
+
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 {
+            return Err(ParseLevelError::new(1, 1, ParseLevelErrorKind::Empty));
+        }
+        let height = rows.len();
+
+        let mut goals = Vec::new();
+        let mut board = Board::new(width, height, SortedSet::new());
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, cell) in row.into_iter().enumerate() {
+                let pos = Ix2(x, y);
+                board.add_particle(cell.particle, pos);
+                board.set_lifetime(pos, cell.lifetime);
+                board.add_obstacle(cell.obstacle, pos);
+                if cell.goal {
+                    goals.push(OrdIx2::from(pos));
+                }
+                if let Some((role, label)) = cell.membership {
+                    let entry = containers.entry(label).or_default();
+                    entry.first_member_at.get_or_insert(cell.at);
+                    match role {
+                        Role::Proton => entry.protons.push(pos.into()),
+                        Role::Neutron => entry.neutrons.push(pos.into()),
+                        Role::Both => {
+                            entry.protons.push(pos.into());
+                            entry.neutrons.push(pos.into());
+                        }
+                        Role::Quark => entry.quarks.push(pos.into()),
+                    }
+                }
+            }
+        }
+        board.goals = SortedSet::from_unsorted(goals);
+
+        for (label, entry) in containers {
+            let is_nucleus = !entry.protons.is_empty() || !entry.neutrons.is_empty();
+            if !entry.quarks.is_empty() {
+                let (line, column) = entry.first_member_at.unwrap_or((1, 1));
+                if is_nucleus || entry.declared_at.is_some() {
+                    return Err(ParseLevelError::new(
+                        line,
+                        column,
+                        ParseLevelErrorKind::MixedMembership(label),
+                    ));
+                }
+                let hadron = Hadron::new(&board, SortedSet::from_unsorted(entry.quarks)).map_err(
+                    |error| {
+                        ParseLevelError::new(
+                            line,
+                            column,
+                            ParseLevelErrorKind::Confinement(label, error),
+                        )
+                    },
+                )?;
+                board.add_container(hadron.into());
+                continue;
+            }
+            if !is_nucleus {
+                let (line, column) = entry.declared_at.unwrap_or((1, 1));
+                return Err(ParseLevelError::new(
+                    line,
+                    column,
+                    ParseLevelErrorKind::EmptyNucleus(label),
+                ));
+            }
+            board.add_container(
+                NucleusParticle::new(
+                    entry.antiness,
+                    SortedSet::from_unsorted(entry.protons),
+                    SortedSet::from_unsorted(entry.neutrons),
+                )
+                .into(),
+            );
+        }
+
+        Ok(board)
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut containers: Vec<&Container> = self.container_lut.values().collect();
+        containers.sort();
+        containers.dedup();
+
+        let mut memberships: BTreeMap<OrdIx2, String> = BTreeMap::new();
+        for (index, container) in containers.iter().enumerate() {
+            let label = index + 1;
+            match container {
+                Container::NucleusParticle(nucleus) => {
+                    for &pos in nucleus.protons().iter() {
+                        memberships.insert(pos, format!("[p{label}]"));
+                    }
+                    for &pos in nucleus.neutrons().iter() {
+                        match memberships.entry(pos) {
+                            Entry::Vacant(entry) => {
+                                entry.insert(format!("[n{label}]"));
+                            }
+                            Entry::Occupied(mut entry) if nucleus.protons().contains(&pos) => {
+                                entry.insert(format!("[b{label}]"));
+                            }
+                            Entry::Occupied(_) => (),
+                        }
+                    }
+                }
+                Container::Hadron(hadron) => {
+                    for &pos in hadron.quarks().iter() {
+                        memberships.insert(pos, format!("[q{label}]"));
+                    }
+                }
+            }
+        }
+
+        let cells: Vec<Vec<String>> = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| {
+                        let pos = Ix2(x, y);
+                        let mut cell = String::new();
+                        cell.push_str(&particle_symbol(&self.particles[pos]));
+                        if let Some(lifetime) = self.lifetime(pos) {
+                            cell.push_str(&format!("~{lifetime}"));
+                        }
+                        cell.extend(obstacle_symbol(&self.obstacles[pos]));
+                        if self.goals.contains(&OrdIx2::from(pos)) {
+                            cell.push('*');
+                        }
+                        if let Some(membership) = memberships.get(&OrdIx2::from(pos)) {
+                            if cell.is_empty() {
+                                cell.push('.');
+                            }
+                            cell.push_str(membership);
+                        }
+                        if cell.is_empty() {
+                            cell.push('.');
+                        }
+                        cell
+                    })
+                    .collect()
+            })
+            .collect();
+        let cell_width = cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(1);
+
+        for row in cells {
+            let row: Vec<_> = row
+                .iter()
+                .map(|cell| format!("{cell:<cell_width$}"))
+                .collect();
+            writeln!(f, "{}", row.join(" ").trim_end())?;
+        }
+        for (index, container) in containers.iter().enumerate() {
+            let antiness = match container {
+                Container::NucleusParticle(nucleus) => nucleus.antiness(),
+                Container::Hadron(_) => continue,
+            };
+            let antiness = match antiness {
+                Antiness::Ordinary => "ordinary",
+                Antiness::Anti => "anti",
+            };
+            writeln!(f, "nucleus {} {antiness}", index + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_round_trip() {
+        let mut board = Board::new(4, 3, vec![Ix2(0, 2).into(), Ix2(3, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(0, 0));
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(1, 1));
+        board.add_particle(Tau::default(), Ix2(0, 2));
+        board.set_lifetime(Ix2(0, 2), 12);
+        board.add_particle(Neutron::new(Antiness::Anti), Ix2(3, 0));
+        board.add_obstacle(Block, Ix2(2, 0));
+        board.add_obstacle(Hole, Ix2(2, 1));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Anti,
+                vec![Ix2(3, 1).into()].into(),
+                vec![Ix2(3, 0).into()].into(),
+            )
+            .into(),
+        );
+
+        let text = board.to_string();
+        assert_eq!(text.parse::<Board>(), Ok(board));
+    }
+
+    #[test]
+    fn level_round_trip_overlapping_nucleus() {
+        let mut board = Board::new(2, 1, vec![].into());
+        board.add_particle(Proton::default(), Ix2(0, 0));
+        board.add_particle(Neutron::default(), Ix2(1, 0));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(0, 0).into()].into(),
+                vec![Ix2(0, 0).into(), Ix2(1, 0).into()].into(),
+            )
+            .into(),
+        );
+
+        let text = board.to_string();
+        assert!(text.starts_with("p[b1] n[n1]"));
+        assert_eq!(text.parse::<Board>(), Ok(board));
+    }
+
+    #[test]
+    fn level_parse() {
+        let board: Board = "; comment\n𝜇  .#\nE*  o[p1]\nnucleus 1 anti\n"
+            .parse()
+            .unwrap();
+        assert_eq!(board.width(), 2);
+        assert_eq!(board.height(), 2);
+        assert_eq!(board.particles().get([0, 0]), Some(&Muon::default().into()));
+        assert_eq!(
+            board.particles().get([0, 1]),
+            Some(&Electron::new(Antiness::Anti).into())
+        );
+        assert_eq!(board.obstacles().get([1, 0]), Some(&Block.into()));
+        assert_eq!(board.obstacles().get([1, 1]), Some(&Hole.into()));
+        assert!(board.is_solved());
+        assert_eq!(board.charge(Ix2(1, 1)), -3);
+    }
+
+    #[test]
+    fn level_parse_errors() {
+        let error = ". .\n. e x\n".parse::<Board>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 5));
+        assert_eq!(error.kind(), &ParseLevelErrorKind::UnexpectedChar('x'));
+
+        let error = ". .\n.\n".parse::<Board>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+
+        let error = ". e*q\n".parse::<Board>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 5));
+
+        let error = ". .[p1\n".parse::<Board>().unwrap_err();
+        assert_eq!(error.kind(), &ParseLevelErrorKind::UnterminatedMembership);
+
+        let error = ".\nnucleus 1\nnucleus 1 anti\n"
+            .parse::<Board>()
+            .unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 9));
+    }
+
+    #[test]
+    fn level_hadron() {
+        let board: Board = "ur[q1] ug[q1]\ndb[q1] DR\n".parse().unwrap();
+        assert_eq!(
+            board.particles().get([1, 1]),
+            Some(&DownQuark::new(Antiness::Anti, Colour::AntiRed).into())
+        );
+        assert_eq!(board.charge(Ix2(0, 1)), 3);
+        assert_eq!(board.to_string().parse::<Board>(), Ok(board));
+
+        let error = "ur[q1] ug[q1]\ndg[q1] .\n".parse::<Board>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 1));
+        assert_eq!(
+            error.kind(),
+            &ParseLevelErrorKind::Confinement("1".to_string(), ConfinementError::Coloured(None))
+        );
+
+        let error = "u .\n".parse::<Board>().unwrap_err();
+        assert_eq!(error.kind(), &ParseLevelErrorKind::MissingColour);
+    }
+}
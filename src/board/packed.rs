@@ -0,0 +1,364 @@
+//! Compact encoding of the boards of one level.
+//!
+//! The search stores and hashes every board it visits, while the size and the
+//! goals never change and obstacles are only ever cleared. A [`PackedLevel`]
+//! keeps those once, and each [`PackedBoard`] only the parts that move.
+
+use std::collections::BTreeSet;
+use std::hash::{Hash, Hasher};
+
+use ndarray::Ix2;
+use sorted_vec::SortedSet;
+
+use crate::container::{contents_positions, Container, ContainerTrait, Hadron};
+use crate::obstacle::{block::Block, hole::Hole, Obstacle};
+use crate::ordered::OrdIx2;
+use crate::particle::{DownQuark, Electron, Muon, Neutron, Particle, Proton, Tau, UpQuark};
+use crate::property::{AntiTrait, Antiness, Colour, ColourTrait};
+
+use super::{Board, Symmetry};
+
+/// Bits per cell for the particle kind and its antiness.
+const PARTICLE_BITS: usize = 4;
+/// Bits per obstacle cell for empty, block or hole.
+const OBSTACLE_BITS: usize = 2;
+const ANTI: u64 = 0b1000;
+
+/// The parts shared by every board reachable from a root.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct PackedLevel {
+    width: usize,
+    height: usize,
+    goals: SortedSet<OrdIx2>,
+    /// Cells that hold an obstacle on the root or on one of its images.
+    obstacle_cells: Vec<Ix2>,
+}
+
+/// A board packed by a [`PackedLevel`]: four bits per cell for the particle,
+/// two bits per obstacle cell of the level, and the rarer quark colours,
+/// lifetimes and containers on the side.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedBoard {
+    /// The Zobrist hash of the board it was packed from. Not stored, since
+    /// [`PackedLevel::repack`] recomputes it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    zobrist: u64,
+    particles: Box<[u64]>,
+    obstacles: Box<[u64]>,
+    /// Colour of each quark, in cell order.
+    colours: Box<[Colour]>,
+    /// Cell index and remaining lifetime of each unstable particle.
+    lifetimes: Box<[(u32, u32)]>,
+    containers: Box<[Container]>,
+}
+
+impl PackedLevel {
+    /// The level of `root`, able to pack every board reachable from it and
+    /// their images under `symmetries`.
+    pub fn new(root: &Board, symmetries: &[Symmetry]) -> Self {
+        let obstacle_cells: BTreeSet<OrdIx2> = root
+            .images(symmetries)
+            .iter()
+            .flat_map(|image| {
+                image
+                    .obstacles
+                    .indexed_iter()
+                    .filter(|(_pos, obstacle)| !matches!(obstacle, Obstacle::Empty(_)))
+                    .map(|((x, y), _obstacle)| OrdIx2::from(Ix2(x, y)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        Self {
+            width: root.width,
+            height: root.height,
+            goals: root.goals.clone(),
+            obstacle_cells: obstacle_cells.into_iter().map(|pos| *pos).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn goals(&self) -> &SortedSet<OrdIx2> {
+        &self.goals
+    }
+
+    fn cell(&self, pos: Ix2) -> u32 {
+        (pos[0] * self.height + pos[1]) as u32
+    }
+
+    fn pos(&self, cell: u32) -> Ix2 {
+        let cell = cell as usize;
+        Ix2(cell / self.height, cell % self.height)
+    }
+
+    /// Packs a board of this level.
+    ///
+    /// # Panics
+    ///
+    /// If `board` has a different size, or an obstacle on a cell where neither
+    /// the root nor any of its images has one. Boards reachable from the root
+    /// never do, since obstacles are only ever cleared.
+    pub fn pack(&self, board: &Board) -> PackedBoard {
+        assert_eq!(
+            (board.width, board.height),
+            (self.width, self.height),
+            "board of a different level"
+        );
+
+        let mut particles = vec![0; words(self.width * self.height, PARTICLE_BITS)];
+        let mut colours = Vec::new();
+        for (cell, particle) in board.particles.iter().enumerate() {
+            let (code, colour) = particle_code(particle);
+            set_bits(&mut particles, cell, PARTICLE_BITS, code);
+            colours.extend(colour);
+        }
+
+        let mut obstacles = vec![0; words(self.obstacle_cells.len(), OBSTACLE_BITS)];
+        for (i, &pos) in self.obstacle_cells.iter().enumerate() {
+            set_bits(
+                &mut obstacles,
+                i,
+                OBSTACLE_BITS,
+                obstacle_code(board.obstacles[pos]),
+            );
+        }
+        let packed_obstacles = self
+            .obstacle_cells
+            .iter()
+            .filter(|&&pos| !matches!(board.obstacles[pos], Obstacle::Empty(_)))
+            .count();
+        let board_obstacles = board
+            .obstacles
+            .iter()
+            .filter(|obstacle| !matches!(obstacle, Obstacle::Empty(_)))
+            .count();
+        assert_eq!(
+            packed_obstacles, board_obstacles,
+            "obstacle outside the level's obstacle cells"
+        );
+
+        let lifetimes = board
+            .lifetimes
+            .iter()
+            .map(|(&pos, &lifetime)| (self.cell(*pos), lifetime))
+            .collect();
+        let containers: BTreeSet<&Container> = board.container_lut.values().collect();
+
+        PackedBoard {
+            zobrist: board.zobrist,
+            particles: particles.into(),
+            obstacles: obstacles.into(),
+            colours: colours.into(),
+            lifetimes,
+            containers: containers.into_iter().cloned().collect(),
+        }
+    }
+
+    /// [`Board::is_solved`] without unpacking.
+    pub fn is_solved(&self, packed: &PackedBoard) -> bool {
+        self.goals
+            .iter()
+            .all(|&goal| get_bits(&packed.particles, self.cell(*goal) as usize, PARTICLE_BITS) != 0)
+    }
+
+    /// `packed` with its hash recomputed, for boards that were deserialized,
+    /// or `None` if it does not fit this level or holds a hadron that does not
+    /// match its quarks.
+    pub fn repack(&self, packed: &PackedBoard) -> Option<PackedBoard> {
+        let cells = self.width * self.height;
+        if packed.particles.len() != words(cells, PARTICLE_BITS)
+            || packed.obstacles.len() != words(self.obstacle_cells.len(), OBSTACLE_BITS)
+        {
+            return None;
+        }
+        let quarks = (0..cells)
+            .filter(|&cell| get_bits(&packed.particles, cell, PARTICLE_BITS) & !ANTI >= 6)
+            .count();
+        let obstacles_valid = (0..self.obstacle_cells.len())
+            .all(|i| get_bits(&packed.obstacles, i, OBSTACLE_BITS) <= 2);
+        let lifetimes_valid = packed
+            .lifetimes
+            .iter()
+            .all(|&(cell, _lifetime)| (cell as usize) < cells);
+        let containers_valid = packed.containers.iter().all(|container| {
+            contents_positions(container.contents())
+                .iter()
+                .all(|pos| pos[0] < self.width && pos[1] < self.height)
+        });
+        if packed.colours.len() != quarks
+            || !obstacles_valid
+            || !lifetimes_valid
+            || !containers_valid
+        {
+            return None;
+        }
+
+        let board = self.unpack(packed);
+        let hadrons_valid = packed.containers.iter().all(|container| match container {
+            Container::Hadron(hadron) => {
+                Hadron::new(&board, hadron.quarks().clone()).as_ref() == Ok(hadron)
+            }
+            _ => true,
+        });
+        if !hadrons_valid {
+            return None;
+        }
+
+        Some(self.pack(&board))
+    }
+
+    /// The board `packed` was packed from.
+    pub fn unpack(&self, packed: &PackedBoard) -> Board {
+        let mut board = Board::new(self.width, self.height, self.goals.clone());
+
+        let mut colours = packed.colours.iter().copied();
+        for cell in 0..self.width * self.height {
+            let code = get_bits(&packed.particles, cell, PARTICLE_BITS);
+            if code != 0 {
+                board.add_particle(
+                    particle_from_code(code, &mut colours),
+                    self.pos(cell as u32),
+                );
+            }
+        }
+        for (i, &pos) in self.obstacle_cells.iter().enumerate() {
+            board.add_obstacle(
+                obstacle_from_code(get_bits(&packed.obstacles, i, OBSTACLE_BITS)),
+                pos,
+            );
+        }
+        for &(cell, lifetime) in packed.lifetimes.iter() {
+            board.set_lifetime(self.pos(cell), lifetime);
+        }
+        for container in packed.containers.iter() {
+            board.add_container(container.clone());
+        }
+
+        board
+    }
+}
+
+impl Hash for PackedBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist);
+    }
+}
+
+fn words(fields: usize, bits: usize) -> usize {
+    (fields * bits).div_ceil(u64::BITS as usize)
+}
+
+fn set_bits(words: &mut [u64], field: usize, bits: usize, value: u64) {
+    let per_word = u64::BITS as usize / bits;
+    let shift = field % per_word * bits;
+    words[field / per_word] |= value << shift;
+}
+
+fn get_bits(words: &[u64], field: usize, bits: usize) -> u64 {
+    let per_word = u64::BITS as usize / bits;
+    let shift = field % per_word * bits;
+    (words[field / per_word] >> shift) & ((1 << bits) - 1)
+}
+
+/// Kind in the low three bits, antiness in the fourth, and the colour of a
+/// quark, which does not fit.
+fn particle_code(particle: &Particle) -> (u64, Option<Colour>) {
+    let (kind, anti, colour) = match particle {
+        Particle::Empty(_) => return (0, None),
+        Particle::Electron(p) => (1, p.anti(), None),
+        Particle::Muon(p) => (2, p.anti(), None),
+        Particle::Tau(p) => (3, p.anti(), None),
+        Particle::Neutron(p) => (4, p.anti(), None),
+        Particle::Proton(p) => (5, p.anti(), None),
+        Particle::UpQuark(p) => (6, p.anti(), Some(p.colour())),
+        Particle::DownQuark(p) => (7, p.anti(), Some(p.colour())),
+    };
+    match anti {
+        Antiness::Ordinary => (kind, colour),
+        Antiness::Anti => (kind | ANTI, colour),
+    }
+}
+
+fn particle_from_code(code: u64, colours: &mut impl Iterator<Item = Colour>) -> Particle {
+    let anti = match code & ANTI {
+        0 => Antiness::Ordinary,
+        _ => Antiness::Anti,
+    };
+    let mut colour = || colours.next().expect("a colour for every quark");
+    match code & !ANTI {
+        0 => Particle::default(),
+        1 => Electron::new(anti).into(),
+        2 => Muon::new(anti).into(),
+        3 => Tau::new(anti).into(),
+        4 => Neutron::new(anti).into(),
+        5 => Proton::new(anti).into(),
+        6 => UpQuark::new(anti, colour()).into(),
+        7 => DownQuark::new(anti, colour()).into(),
+        _ => unreachable!("particle codes have four bits"),
+    }
+}
+
+fn obstacle_code(obstacle: Obstacle) -> u64 {
+    match obstacle {
+        Obstacle::Empty(_) => 0,
+        Obstacle::Block(_) => 1,
+        Obstacle::Hole(_) => 2,
+    }
+}
+
+fn obstacle_from_code(code: u64) -> Obstacle {
+    match code {
+        0 => Obstacle::default(),
+        1 => Block.into(),
+        2 => Hole.into(),
+        _ => unreachable!("no obstacle has code {code}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::NucleusParticle;
+
+    use super::*;
+
+    #[test]
+    fn pack_round_trip() {
+        let mut board = Board::new(5, 4, vec![Ix2(0, 3).into()].into());
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(1, 0));
+        board.set_lifetime(Ix2(1, 0), 3);
+        board.add_particle(UpQuark::new(Antiness::Ordinary, Colour::Red), Ix2(4, 3));
+        board.add_particle(DownQuark::new(Antiness::Anti, Colour::AntiBlue), Ix2(0, 1));
+        board.add_particle(Proton::default(), Ix2(2, 2));
+        board.add_particle(Neutron::default(), Ix2(3, 2));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(2, 2).into()].into(),
+                vec![Ix2(3, 2).into()].into(),
+            )
+            .into(),
+        );
+        board.add_obstacle(Block, Ix2(4, 0));
+        board.add_obstacle(Hole, Ix2(2, 1));
+
+        let level = PackedLevel::new(&board, &[]);
+        let packed = level.pack(&board);
+        assert_eq!(level.unpack(&packed), board);
+        assert!(!level.is_solved(&packed));
+
+        let mut cleared = board.clone();
+        cleared.remove_obstacle(Ix2(4, 0));
+        cleared.move_particle(Ix2(1, 0), Ix2(0, 3));
+        assert_ne!(level.pack(&cleared), packed);
+        assert_eq!(level.unpack(&level.pack(&cleared)), cleared);
+        assert!(level.is_solved(&level.pack(&cleared)));
+    }
+}
@@ -0,0 +1,285 @@
+//! Versioned serde schema for [`Board`].
+//!
+//! Cells are stored sparsely and containers are stored once each, so the
+//! `container_lut` is rebuilt through [`Board::add_container`] on load rather
+//! than written out with its non-string keys.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use ndarray::Ix2;
+use serde::{Deserialize, Serialize};
+use sorted_vec::SortedSet;
+
+use crate::container::{
+    contents_positions, Component, ConfinementError, Container, ContainerTrait, Hadron,
+    NucleusParticle,
+};
+use crate::obstacle::Obstacle;
+use crate::ordered::OrdIx2;
+use crate::particle::Particle;
+
+use super::Board;
+
+/// Version written by [`Board`]'s `Serialize` implementation. Version 1 had
+/// no `lifetimes` and is still accepted.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Most cells a loaded board may have, so a malformed file cannot make
+/// [`Board::new`] allocate without bound.
+pub const MAX_CELLS: usize = 1 << 20;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum BoardSchemaError {
+    UnsupportedVersion(u32),
+    /// The board has more than [`MAX_CELLS`] cells.
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+    OutOfBounds(OrdIx2),
+    /// A nucleus member is not a nucleon of the right kind, or the cell
+    /// already belongs to another container.
+    Nucleus(OrdIx2),
+    Confinement(ConfinementError),
+}
+
+impl Error for BoardSchemaError {}
+
+impl Display for BoardSchemaError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported board schema version {version}, expected at most {SCHEMA_VERSION}"
+            ),
+            Self::TooLarge { width, height } => write!(
+                f,
+                "board of {width} by {height} has more than {MAX_CELLS} cells"
+            ),
+            Self::OutOfBounds(pos) => {
+                write!(f, "position ({}, {}) is outside the board", pos[0], pos[1])
+            }
+            Self::Nucleus(pos) => write!(
+                f,
+                "invalid nucleus: position ({}, {}) does not hold a nucleon of its kind \
+                 or is already in a container",
+                pos[0], pos[1]
+            ),
+            Self::Confinement(error) => write!(f, "invalid hadron: {error}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct BoardRepr {
+    version: u32,
+    width: usize,
+    height: usize,
+    goals: Vec<OrdIx2>,
+    particles: Vec<(OrdIx2, Particle)>,
+    obstacles: Vec<(OrdIx2, Obstacle)>,
+    containers: Vec<Container>,
+    #[serde(default)]
+    lifetimes: Vec<(OrdIx2, u32)>,
+}
+
+impl From<Board> for BoardRepr {
+    fn from(board: Board) -> Self {
+        let particles = board
+            .particles
+            .indexed_iter()
+            .filter(|(_, particle)| !matches!(particle, Particle::Empty(_)))
+            .map(|((x, y), &particle)| (Ix2(x, y).into(), particle))
+            .collect();
+        let obstacles = board
+            .obstacles
+            .indexed_iter()
+            .filter(|(_, obstacle)| !matches!(obstacle, Obstacle::Empty(_)))
+            .map(|((x, y), &obstacle)| (Ix2(x, y).into(), obstacle))
+            .collect();
+        let containers: BTreeSet<Container> = board.container_lut.into_values().collect();
+
+        Self {
+            version: SCHEMA_VERSION,
+            width: board.width,
+            height: board.height,
+            goals: board.goals.into_vec(),
+            particles,
+            obstacles,
+            containers: containers.into_iter().collect(),
+            lifetimes: board.lifetimes.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<BoardRepr> for Board {
+    type Error = BoardSchemaError;
+
+    fn try_from(repr: BoardRepr) -> Result<Self, Self::Error> {
+        if !(1..=SCHEMA_VERSION).contains(&repr.version) {
+            return Err(BoardSchemaError::UnsupportedVersion(repr.version));
+        }
+        if repr
+            .width
+            .checked_mul(repr.height)
+            .is_none_or(|cells| cells > MAX_CELLS)
+        {
+            return Err(BoardSchemaError::TooLarge {
+                width: repr.width,
+                height: repr.height,
+            });
+        }
+
+        let check = |pos: OrdIx2| match pos[0] < repr.width && pos[1] < repr.height {
+            true => Ok(pos),
+            false => Err(BoardSchemaError::OutOfBounds(pos)),
+        };
+        for &goal in &repr.goals {
+            check(goal)?;
+        }
+        for container in &repr.containers {
+            for pos in contents_positions(container.contents()) {
+                check(pos)?;
+            }
+        }
+
+        let mut board = Board::new(
+            repr.width,
+            repr.height,
+            SortedSet::from_unsorted(repr.goals),
+        );
+        for (pos, particle) in repr.particles {
+            board.add_particle(particle, *check(pos)?);
+        }
+        for (pos, obstacle) in repr.obstacles {
+            board.add_obstacle(obstacle, *check(pos)?);
+        }
+        for container in repr.containers {
+            let container = match container {
+                Container::Hadron(hadron) => Hadron::new(&board, hadron.quarks().clone())
+                    .map_err(BoardSchemaError::Confinement)?
+                    .into(),
+                Container::NucleusParticle(nucleus) => check_nucleus(&board, nucleus)?.into(),
+            };
+            board.add_container(container);
+        }
+        for (pos, lifetime) in repr.lifetimes {
+            board.set_lifetime(*check(pos)?, lifetime);
+        }
+
+        Ok(board)
+    }
+}
+
+/// Checks that every proton cell holds a proton and every neutron cell a
+/// neutron, and that none of them is taken by another container yet. A cell
+/// listed as both fails the first check for one of the two.
+fn check_nucleus(
+    board: &Board,
+    nucleus: NucleusParticle,
+) -> Result<NucleusParticle, BoardSchemaError> {
+    let members = nucleus
+        .protons()
+        .iter()
+        .map(|&pos| (pos, true))
+        .chain(nucleus.neutrons().iter().map(|&pos| (pos, false)));
+    for (pos, proton) in members {
+        let nucleon = match board.particles()[*pos] {
+            Particle::Proton(_) => proton,
+            Particle::Neutron(_) => !proton,
+            _ => false,
+        };
+        if !nucleon || board.containers().contains_key(&Component::Particle(pos)) {
+            return Err(BoardSchemaError::Nucleus(pos));
+        }
+    }
+    Ok(nucleus)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::obstacle::hole::Hole;
+    use crate::particle::{Electron, Neutron, Proton};
+    use crate::property::Antiness;
+
+    use super::*;
+
+    #[test]
+    fn board_json_round_trip() {
+        let mut board = Board::new(4, 3, vec![Ix2(0, 2).into()].into());
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(1, 1));
+        board.add_particle(Neutron::default(), Ix2(3, 0));
+        board.set_lifetime(Ix2(3, 0), 4);
+        board.add_particle(Proton::default(), Ix2(3, 1));
+        board.add_obstacle(Hole, Ix2(2, 1));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(3, 1).into()].into(),
+                vec![Ix2(3, 0).into()].into(),
+            )
+            .into(),
+        );
+
+        let json = serde_json::to_string(&board).unwrap();
+        let loaded: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.charge(Ix2(3, 0)), 3);
+    }
+
+    #[test]
+    fn board_json_rebinds_hadrons() {
+        let json = r#"{"version":2,"width":2,"height":1,"goals":[],"particles":[[[0,0],{"UpQuark":{"anti":"Ordinary","colour":"Red"}}],[[1,0],{"UpQuark":{"anti":"Anti","colour":"AntiRed"}}]],"obstacles":[],"containers":[{"Hadron":{"charge":5,"quarks":[[0,0],[1,0]]}}]}"#;
+        let board: Board = serde_json::from_str(json).unwrap();
+        assert_eq!(board.charge(Ix2(0, 0)), 0);
+
+        let json = json.replace(r#","colour":"AntiRed""#, r#","colour":"AntiGreen""#);
+        let error = serde_json::from_str::<Board>(&json).unwrap_err();
+        assert!(error.to_string().contains("colour neutral"));
+    }
+
+    #[test]
+    fn board_json_checks_nuclei() {
+        let json = r#"{"version":2,"width":2,"height":2,"goals":[],"particles":[[[0,0],{"Proton":{"anti":"Ordinary"}}],[[1,0],{"Neutron":{"anti":"Ordinary"}}],[[0,1],{"Neutron":{"anti":"Ordinary"}}]],"obstacles":[],"containers":[{"NucleusParticle":{"antiness":"Ordinary","protons":[[0,0]],"neutrons":[[1,0]]}}]}"#;
+        let board: Board = serde_json::from_str(json).unwrap();
+        assert_eq!(board.charge(Ix2(1, 0)), 3);
+
+        let swapped = json.replace(
+            r#""protons":[[0,0]],"neutrons":[[1,0]]"#,
+            r#""protons":[[1,0]],"neutrons":[[0,0]]"#,
+        );
+        let error = serde_json::from_str::<Board>(&swapped).unwrap_err();
+        assert!(error.to_string().contains("invalid nucleus"));
+
+        let twice = json.replace(r#""neutrons":[[1,0]]"#, r#""neutrons":[[1,0],[0,0]]"#);
+        assert!(serde_json::from_str::<Board>(&twice).is_err());
+
+        let shared = json.replace(
+            r#"}}]}"#,
+            r#"}},{"NucleusParticle":{"antiness":"Ordinary","protons":[[0,0]],"neutrons":[[0,1]]}}]}"#,
+        );
+        let error = serde_json::from_str::<Board>(&shared).unwrap_err();
+        assert!(error.to_string().contains("(0, 0)"));
+    }
+
+    #[test]
+    fn board_json_rejects_unknown_version() {
+        let json = r#"{"version":99,"width":1,"height":1,"goals":[],"particles":[],"obstacles":[],"containers":[]}"#;
+        let error = serde_json::from_str::<Board>(json).unwrap_err();
+        assert!(error.to_string().contains("version 99"));
+
+        let json = r#"{"version":1,"width":1,"height":1,"goals":[[0,1]],"particles":[],"obstacles":[],"containers":[]}"#;
+        assert!(serde_json::from_str::<Board>(json).is_err());
+
+        let json = r#"{"version":1,"width":1,"height":1,"goals":[[0,0]],"particles":[],"obstacles":[],"containers":[]}"#;
+        assert!(serde_json::from_str::<Board>(json).is_ok());
+
+        let huge = json.replace(r#""height":1"#, &format!(r#""height":{}"#, usize::MAX));
+        let error = serde_json::from_str::<Board>(&huge).unwrap_err();
+        assert!(error.to_string().contains("cells"));
+        let large = json.replace(r#""width":1,"height":1"#, r#""width":1025,"height":1024"#);
+        assert!(serde_json::from_str::<Board>(&large).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Mirror and transpose transforms of a [`Board`].
+//!
+//! The rules treat every direction alike, so a board and its mirror image
+//! need the same number of moves whenever the goals map onto themselves.
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use ndarray::Ix2;
+use sorted_vec::SortedSet;
+
+use crate::container::{Container, ContainerTrait};
+use crate::obstacle::Obstacle;
+use crate::ordered::OrdIx2;
+use crate::particle::Particle;
+
+use super::Board;
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symmetry {
+    /// Swaps left and right.
+    MirrorHorizontal,
+    /// Swaps top and bottom.
+    MirrorVertical,
+    /// Swaps rows and columns. Only square boards can be transposed.
+    Transpose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 3] = [
+        Symmetry::MirrorHorizontal,
+        Symmetry::MirrorVertical,
+        Symmetry::Transpose,
+    ];
+
+    /// Where `pos` ends up on a `width` by `height` board.
+    pub fn map(self, width: usize, height: usize, pos: Ix2) -> Ix2 {
+        match self {
+            Symmetry::MirrorHorizontal => Ix2(width - 1 - pos[0], pos[1]),
+            Symmetry::MirrorVertical => Ix2(pos[0], height - 1 - pos[1]),
+            Symmetry::Transpose => Ix2(pos[1], pos[0]),
+        }
+    }
+}
+
+impl Board {
+    /// The board with every particle, lifetime, obstacle, goal and container
+    /// moved by `symmetry`, or `None` if a non-square board is transposed.
+    pub fn transformed(&self, symmetry: Symmetry) -> Option<Board> {
+        if symmetry == Symmetry::Transpose && self.width != self.height {
+            return None;
+        }
+        let map = |pos: OrdIx2| OrdIx2::from(symmetry.map(self.width, self.height, *pos));
+
+        let goals = self.goals.iter().map(|&goal| map(goal)).collect();
+        let mut board = Board::new(self.width, self.height, SortedSet::from_unsorted(goals));
+        for ((x, y), &particle) in self.particles.indexed_iter() {
+            if !matches!(particle, Particle::Empty(_)) {
+                board.add_particle(particle, *map(Ix2(x, y).into()));
+            }
+        }
+        for ((x, y), &obstacle) in self.obstacles.indexed_iter() {
+            if !matches!(obstacle, Obstacle::Empty(_)) {
+                board.add_obstacle(obstacle, *map(Ix2(x, y).into()));
+            }
+        }
+        for (&pos, &lifetime) in &self.lifetimes {
+            board.set_lifetime(*map(pos), lifetime);
+        }
+        let containers: BTreeSet<&Container> = self.container_lut.values().collect();
+        for container in containers {
+            board.add_container(container.mapped(&map));
+        }
+
+        Some(board)
+    }
+
+    /// Whether the goals map onto themselves under `symmetry`, so that the
+    /// board and its image are equally far from solved.
+    pub fn is_level_symmetric(&self, symmetry: Symmetry) -> bool {
+        self.transformed(symmetry)
+            .is_some_and(|board| board.goals == self.goals)
+    }
+
+    /// Every board reachable from this one by repeating `symmetries`,
+    /// starting with the board itself.
+    pub fn images(&self, symmetries: &[Symmetry]) -> Vec<Board> {
+        let mut images = vec![self.clone()];
+        let mut i = 0;
+        while i < images.len() {
+            for &symmetry in symmetries {
+                if let Some(image) = images[i].transformed(symmetry) {
+                    if !images.contains(&image) {
+                        images.push(image);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        images
+    }
+
+    /// The smallest of [`Board::images`], so boards that are images of each
+    /// other share one representative.
+    pub fn canonical(&self, symmetries: &[Symmetry]) -> Board {
+        if symmetries.is_empty() {
+            return self.clone();
+        }
+        self.images(symmetries)
+            .into_iter()
+            .min_by(|a, b| a.cmp_state(b))
+            .expect("a board is its own image")
+    }
+
+    /// Total order over boards of the same size.
+    fn cmp_state(&self, other: &Board) -> Ordering {
+        self.particles
+            .iter()
+            .cmp(other.particles.iter())
+            .then_with(|| self.obstacles.iter().cmp(other.obstacles.iter()))
+            .then_with(|| self.lifetimes.cmp(&other.lifetimes))
+            .then_with(|| self.container_lut.cmp(&other.container_lut))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::NucleusParticle;
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Proton};
+    use crate::property::Antiness;
+
+    use super::*;
+
+    #[test]
+    fn board_transforms() {
+        let mut board = Board::new(4, 3, vec![Ix2(0, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(1, 0));
+        board.set_lifetime(Ix2(1, 0), 2);
+        board.add_obstacle(Hole, Ix2(3, 1));
+        board.add_particle(Proton::default(), Ix2(1, 2));
+        board.add_particle(Neutron::default(), Ix2(2, 2));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(1, 2).into()].into(),
+                vec![Ix2(2, 2).into()].into(),
+            )
+            .into(),
+        );
+
+        let mirrored = board.transformed(Symmetry::MirrorHorizontal).unwrap();
+        assert_eq!(
+            mirrored.particles()[[2, 0]],
+            Particle::from(Electron::default())
+        );
+        assert_eq!(mirrored.lifetime(Ix2(2, 0)), Some(2));
+        assert_eq!(mirrored.obstacles()[[0, 1]], Obstacle::from(Hole));
+        assert_eq!(mirrored.goals().to_vec(), vec![Ix2(3, 2).into()]);
+        assert_eq!(mirrored.charge(Ix2(1, 2)), 3);
+        assert_eq!(
+            mirrored.transformed(Symmetry::MirrorHorizontal),
+            Some(board.clone())
+        );
+
+        let flipped = board.transformed(Symmetry::MirrorVertical).unwrap();
+        assert_eq!(flipped.goals().to_vec(), vec![Ix2(0, 0).into()]);
+        assert_eq!(board.transformed(Symmetry::Transpose), None);
+        assert!(!board.is_level_symmetric(Symmetry::MirrorHorizontal));
+    }
+
+    #[test]
+    fn board_canonical() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_obstacle(Block, Ix2(0, 0));
+        let mut left = board.clone();
+        left.add_particle(Neutron::default(), Ix2(1, 3));
+        let mut right = board.clone();
+        right.add_particle(Neutron::default(), Ix2(3, 1));
+
+        assert!(board.is_level_symmetric(Symmetry::Transpose));
+        assert_eq!(left.images(&Symmetry::ALL).len(), 8);
+        assert_eq!(
+            left.canonical(&[Symmetry::Transpose]),
+            right.canonical(&[Symmetry::Transpose])
+        );
+        assert_ne!(
+            left.canonical(&[Symmetry::MirrorVertical]),
+            right.canonical(&[Symmetry::MirrorVertical])
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Zobrist hash of a [`Board`].
+//!
+//! Every particle, obstacle, lifetime and container entry on the board has a
+//! fixed pseudo-random key and the hash is the XOR of the keys present. Each
+//! write goes through the setters here, which XOR out the old key and XOR in
+//! the new one, so hashing a board never walks its grids.
+
+use std::hash::{Hash, Hasher};
+use std::mem::{self, Discriminant};
+
+use ndarray::Ix2;
+
+use crate::container::{Component, Container, ContainerTrait};
+use crate::obstacle::Obstacle;
+use crate::ordered::OrdIx2;
+use crate::particle::Particle;
+
+use super::Board;
+
+/// Tells the keys of different parts of the board apart.
+#[derive(Hash)]
+enum Part<'a> {
+    Particle(OrdIx2, Particle),
+    Obstacle(OrdIx2, Obstacle),
+    Lifetime(OrdIx2, u32),
+    /// A component with the kind and first member of its container. The rest
+    /// of the container follows from the particles, which have keys of their
+    /// own, so this is enough to tell containers apart without hashing them.
+    Container(
+        &'a Component,
+        Discriminant<Container>,
+        Option<&'a Component>,
+    ),
+}
+
+impl<'a> Part<'a> {
+    fn container(component: &'a Component, container: &'a Container) -> Self {
+        Part::Container(
+            component,
+            mem::discriminant(container),
+            container.contents().first(),
+        )
+    }
+}
+
+impl Part<'_> {
+    /// The same key for the same part in every run and every build.
+    fn key(&self) -> u64 {
+        let mut hasher = SplitMix64(0);
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Mixes every integer written to it into its state with splitmix64. Unlike
+/// the standard library's hashers its output is fixed, and widening each
+/// write to 64 bits keeps it independent of the integer types `Hash`
+/// implementations happen to write.
+struct SplitMix64(u64);
+
+impl Hasher for SplitMix64 {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(byte.into());
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(i.into());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i.into());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i.into());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        let mut z = (self.0 ^ i).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        self.0 = z ^ (z >> 31);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write_i64(i.into());
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_i64(i.into());
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_i64(i.into());
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist);
+    }
+}
+
+impl Board {
+    /// Sets the particle at `pos` and returns the one it replaces.
+    pub(super) fn put_particle(&mut self, pos: Ix2, particle: Particle) -> Particle {
+        let before = std::mem::replace(&mut self.particles[pos], particle);
+        if !matches!(before, Particle::Empty(_)) {
+            self.zobrist ^= Part::Particle(pos.into(), before).key();
+        }
+        if !matches!(particle, Particle::Empty(_)) {
+            self.zobrist ^= Part::Particle(pos.into(), particle).key();
+        }
+        before
+    }
+
+    /// Sets the obstacle at `pos` and returns the one it replaces.
+    pub(super) fn put_obstacle(&mut self, pos: Ix2, obstacle: Obstacle) -> Obstacle {
+        let before = std::mem::replace(&mut self.obstacles[pos], obstacle);
+        if !matches!(before, Obstacle::Empty(_)) {
+            self.zobrist ^= Part::Obstacle(pos.into(), before).key();
+        }
+        if !matches!(obstacle, Obstacle::Empty(_)) {
+            self.zobrist ^= Part::Obstacle(pos.into(), obstacle).key();
+        }
+        before
+    }
+
+    /// Sets or clears the lifetime at `pos` and returns the one it replaces.
+    pub(super) fn put_lifetime(&mut self, pos: OrdIx2, lifetime: Option<u32>) -> Option<u32> {
+        let before = match lifetime {
+            Some(lifetime) => self.lifetimes.insert(pos, lifetime),
+            None => self.lifetimes.remove(&pos),
+        };
+        if let Some(before) = before {
+            self.zobrist ^= Part::Lifetime(pos, before).key();
+        }
+        if let Some(lifetime) = lifetime {
+            self.zobrist ^= Part::Lifetime(pos, lifetime).key();
+        }
+        before
+    }
+
+    /// Sets or clears the container `component` is bound in.
+    pub(super) fn put_container(&mut self, component: &Component, container: Option<&Container>) {
+        let before = match container {
+            Some(container) => self
+                .container_lut
+                .insert(component.clone(), container.clone()),
+            None => self.container_lut.remove(component),
+        };
+        if let Some(before) = &before {
+            self.zobrist ^= Part::container(component, before).key();
+        }
+        if let Some(container) = container {
+            self.zobrist ^= Part::container(component, container).key();
+        }
+    }
+
+    /// The hash computed from scratch, which the incremental one always equals.
+    #[cfg(test)]
+    pub(super) fn full_zobrist(&self) -> u64 {
+        let particles = self
+            .particles
+            .indexed_iter()
+            .filter(|(_pos, particle)| !matches!(particle, Particle::Empty(_)))
+            .map(|((x, y), &particle)| Part::Particle(Ix2(x, y).into(), particle).key());
+        let obstacles = self
+            .obstacles
+            .indexed_iter()
+            .filter(|(_pos, obstacle)| !matches!(obstacle, Obstacle::Empty(_)))
+            .map(|((x, y), &obstacle)| Part::Obstacle(Ix2(x, y).into(), obstacle).key());
+        let lifetimes = self
+            .lifetimes
+            .iter()
+            .map(|(&pos, &lifetime)| Part::Lifetime(pos, lifetime).key());
+        let containers = self
+            .container_lut
+            .iter()
+            .map(|(component, container)| Part::container(component, container).key());
+
+        particles
+            .chain(obstacles)
+            .chain(lifetimes)
+            .chain(containers)
+            .fold(0, |hash, key| hash ^ key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
+    use crate::container::NucleusParticle;
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Muon, Neutron, Proton};
+    use crate::property::Antiness;
+
+    use super::*;
+
+    fn hash(board: &Board) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        board.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn incremental_hash() {
+        let mut board = Board::new(5, 5, vec![Ix2(4, 4).into()].into());
+        board.add_particle(Muon::default(), Ix2(0, 0));
+        board.set_lifetime(Ix2(0, 0), 2);
+        board.add_particle(Muon::new(Antiness::Anti), Ix2(0, 3));
+        board.add_particle(Proton::default(), Ix2(2, 2));
+        board.add_particle(Neutron::default(), Ix2(3, 2));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(2, 2).into()].into(),
+                vec![Ix2(3, 2).into()].into(),
+            )
+            .into(),
+        );
+        board.add_obstacle(Block, Ix2(4, 0));
+        board.add_obstacle(Hole, Ix2(1, 4));
+        assert_eq!(board.zobrist, board.full_zobrist());
+
+        let root = board.clone();
+        for m in board.all_moves() {
+            board.apply(&m);
+            assert_eq!(board.zobrist, board.full_zobrist());
+            board.undo(&m);
+            assert_eq!(board, root);
+            assert_eq!(hash(&board), hash(&root));
+        }
+
+        board.move_particle(Ix2(0, 0), Ix2(1, 1));
+        board.annihilate(Ix2(1, 3), 1);
+        board.tick();
+        board.tick();
+        assert_eq!(board.zobrist, board.full_zobrist());
+        assert_ne!(hash(&board), hash(&root));
+
+        board.move_particle(Ix2(1, 1), Ix2(0, 0));
+        board.remove_particle(Ix2(0, 0));
+        board.add_particle(Muon::default(), Ix2(0, 0));
+        assert_eq!(board.zobrist, board.full_zobrist());
+    }
+
+    #[test]
+    fn keys_are_fixed() {
+        let part = Part::Lifetime(Ix2(1, 2).into(), 3);
+        assert_eq!(part.key(), 0x0868_50a6_202e_99d9);
+    }
+}
@@ -1,14 +1,20 @@
 use std::collections::BTreeMap;
 
 use enum_dispatch::enum_dispatch;
+use ndarray::Ix2;
 use sorted_vec::SortedSet;
 
 use crate::board::Board;
+use crate::movement::{Move, MoveBuilder, Outcome};
+use crate::obstacle::Obstacle;
 use crate::ordered::OrdIx2;
-use crate::property::Colour;
+use crate::particle::Particle;
+use crate::property::{Colour, Direction};
 
-use self::nucleus::NucleusParticle;
+pub use self::hadron::{ConfinementError, Hadron};
+pub use self::nucleus::NucleusParticle;
 
+mod hadron;
 mod nucleus;
 
 pub type Contents = SortedSet<Component>;
@@ -24,10 +30,171 @@ pub fn contents_positions(contents: &Contents) -> Vec<OrdIx2> {
     positions
 }
 
+/// Directions a container of the given charge may move in, following the same
+/// field rules as a single charged particle but with the summed charge seen by
+/// all of its cells.
+pub fn electric_directions(board: &Board, container: &impl ContainerTrait) -> Vec<Direction> {
+    let mut ret_directions: Vec<Direction> = Vec::new();
+    let contents = container.contents();
+
+    let x = container.charge()
+        * (board.find_charge_many(contents, Direction::Right)
+            - board.find_charge_many(contents, Direction::Left));
+    if x >= 0 {
+        ret_directions.push(Direction::Left)
+    }
+    if x <= 0 {
+        ret_directions.push(Direction::Right)
+    }
+
+    let y = container.charge()
+        * (board.find_charge_many(contents, Direction::Down)
+            - board.find_charge_many(contents, Direction::Up));
+    if y >= 0 {
+        ret_directions.push(Direction::Up)
+    }
+    if y <= 0 {
+        ret_directions.push(Direction::Down)
+    }
+
+    ret_directions
+}
+
+/// Slides a container as one rigid body until any of its cells would hit a
+/// particle outside the container, a block or the edge of the board. The whole
+/// container falls if any of its cells moves onto a hole. A member that runs
+/// into its free antiparticle annihilates with it like a single particle in
+/// [`slide`](crate::particle::slide), and the rest of the container stays
+/// where it got to.
+pub fn rigid_move(board: &Board, container: &Container, direction: Direction) -> Option<Move> {
+    let contents = container.contents();
+    let positions = contents_positions(contents);
+    let is_member = |pos: Ix2| positions.contains(&pos.into());
+    let origin = Component::Container(contents.clone());
+    let mut builder = MoveBuilder::new(board);
+
+    let mut steps = 0;
+    let mut current: Vec<Ix2> = positions.iter().map(|&i| *i).collect();
+    let mut annihilated = Vec::new();
+    'slide: while let Some(next) = current
+        .iter()
+        .map(|&i| board.move_direction(direction, i))
+        .collect::<Option<Vec<_>>>()
+    {
+        let mut hole = None;
+        let mut blocked = false;
+        for (member, &pos) in next.iter().enumerate() {
+            let other = board.particles().get(pos).unwrap();
+            match other {
+                Particle::Empty(_) => (),
+                _ if is_member(pos) => (),
+                _ if board
+                    .top_container(&Component::Particle(pos.into()))
+                    .is_none()
+                    && board.particles()[*positions[member]].annihilates(other) =>
+                {
+                    annihilated.push((member, pos))
+                }
+                _ => blocked = true,
+            };
+            match board.obstacles().get(pos).unwrap() {
+                Obstacle::Empty(_) => (),
+                Obstacle::Block(_) => blocked = true,
+                Obstacle::Hole(_) => hole = hole.or(Some(pos)),
+            };
+        }
+
+        if !annihilated.is_empty() || blocked {
+            break 'slide;
+        }
+        if let Some(hole) = hole {
+            builder.replace_container(Some(container.clone()), None);
+            for &pos in &positions {
+                builder.remove_particle(*pos);
+            }
+            return Some(builder.finish(origin, direction, Outcome::FellInto(hole.into())));
+        }
+
+        steps += 1;
+        current = next;
+    }
+
+    if steps == 0 && annihilated.is_empty() {
+        return None;
+    }
+
+    let mut after = Some(container.translated(board, direction, steps));
+    for &(member, pos) in &annihilated {
+        after = after.and_then(|after| after.without(current[member].into()));
+        builder.remove_particle(pos);
+        builder.annihilate(pos, 3);
+    }
+    builder.replace_container(Some(container.clone()), after);
+
+    let survivors: Vec<(Ix2, Ix2)> = positions
+        .iter()
+        .zip(&current)
+        .enumerate()
+        .filter(|(member, _)| !annihilated.iter().any(|(gone, _)| gone == member))
+        .map(|(_, (&from_pos, &to_pos))| (*from_pos, to_pos))
+        .collect();
+    for &pos in &positions {
+        if !survivors.iter().any(|&(_, to_pos)| to_pos == *pos) {
+            builder.remove_particle(*pos);
+        }
+    }
+    for &(from_pos, to_pos) in &survivors {
+        builder.set_particle(
+            to_pos,
+            board.particles()[from_pos],
+            board.lifetime(from_pos),
+        );
+    }
+
+    let outcome = match annihilated.first() {
+        Some(&(_, pos)) => Outcome::Annihilated(pos.into()),
+        None => Outcome::SlidTo(current[0].into()),
+    };
+    Some(builder.finish(origin, direction, outcome))
+}
+
+fn map_positions(
+    positions: &SortedSet<OrdIx2>,
+    map: &dyn Fn(OrdIx2) -> OrdIx2,
+) -> SortedSet<OrdIx2> {
+    SortedSet::from_unsorted(positions.iter().map(|&pos| map(pos)).collect())
+}
+
+fn translate_positions(
+    board: &Board,
+    positions: &SortedSet<OrdIx2>,
+    direction: Direction,
+    steps: usize,
+) -> SortedSet<OrdIx2> {
+    positions
+        .iter()
+        .map(|&pos| {
+            let mut pos = *pos;
+            for _ in 0..steps {
+                pos = board
+                    .move_direction(direction, pos)
+                    .expect("translation should stay on the board");
+            }
+            OrdIx2::from(pos)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
 #[enum_dispatch]
 pub trait ContainerTrait {
-    fn all_moves(&self, board: Board) -> Vec<Board>;
+    fn all_moves(&self, board: &Board) -> Vec<Move>;
     fn contents(&self) -> &Contents;
+    fn translated(&self, board: &Board, direction: Direction, steps: usize) -> Container;
+    /// The same container with every cell moved to `map` of its position.
+    fn mapped(&self, map: &dyn Fn(OrdIx2) -> OrdIx2) -> Container;
+    /// What is left bound once the particle at `pos` is gone, if anything.
+    fn without(&self, pos: OrdIx2) -> Option<Container>;
 
     fn charge(&self) -> i32 {
         0
@@ -65,7 +232,7 @@ impl Component {
         positions
     }
 
-    fn all_positions_push<'a>(&'a self, positions: &'a mut Vec<OrdIx2>) -> &Vec<OrdIx2> {
+    fn all_positions_push<'a>(&'a self, positions: &'a mut Vec<OrdIx2>) -> &'a Vec<OrdIx2> {
         match self {
             Component::Particle(i) => positions.push(*i),
             Component::Container(c) => {
@@ -81,6 +248,8 @@ impl Component {
 
 #[enum_dispatch(ContainerTrait)]
 #[derive(PartialEq, Eq, Clone, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Container {
     NucleusParticle(NucleusParticle),
+    Hadron(Hadron),
 }
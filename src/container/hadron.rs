@@ -0,0 +1,269 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use sorted_vec::SortedSet;
+
+use crate::board::Board;
+use crate::movement::Move;
+use crate::ordered::OrdIx2;
+use crate::particle::{Particle, ParticleTrait};
+use crate::property::{AntiTrait, Colour, ColourTrait, Direction};
+
+use super::{
+    electric_directions, map_positions, rigid_move, translate_positions, Component, Container,
+    ContainerTrait, Contents,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ConfinementError {
+    /// Fewer than two quarks, which can never be bound.
+    TooFewQuarks,
+    NotAQuark(OrdIx2),
+    /// A quark with an anticolour or an antiquark with a colour, or either
+    /// without any colour.
+    MismatchedColour(OrdIx2),
+    Coloured(Option<Colour>),
+}
+
+impl Error for ConfinementError {}
+
+impl Display for ConfinementError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::TooFewQuarks => write!(f, "hadron needs at least two quarks"),
+            Self::NotAQuark(pos) => write!(f, "no quark at ({}, {})", pos[0], pos[1]),
+            Self::MismatchedColour(pos) => write!(
+                f,
+                "quark at ({}, {}) does not carry a colour of its antiness",
+                pos[0], pos[1]
+            ),
+            Self::Coloured(Some(colour)) => {
+                write!(f, "hadron is not colour neutral, its colour is {colour:?}")
+            }
+            Self::Coloured(None) => write!(f, "hadron is not colour neutral"),
+        }
+    }
+}
+
+/// A colour neutral group of quarks that moves as one body.
+#[derive(PartialEq, Eq, Clone, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "HadronRepr", into = "HadronRepr")
+)]
+pub struct Hadron {
+    charge: i32,
+    quarks: SortedSet<OrdIx2>,
+    contents: Contents,
+}
+
+impl ContainerTrait for Hadron {
+    fn all_moves(&self, board: &Board) -> Vec<Move> {
+        let container = Container::from(self.clone());
+        electric_directions(board, self)
+            .iter()
+            .filter_map(|&direction| rigid_move(board, &container, direction))
+            .collect()
+    }
+
+    fn contents(&self) -> &Contents {
+        &self.contents
+    }
+
+    fn translated(&self, board: &Board, direction: Direction, steps: usize) -> Container {
+        Self::from_parts(
+            self.charge,
+            translate_positions(board, &self.quarks, direction, steps),
+        )
+        .into()
+    }
+
+    fn mapped(&self, map: &dyn Fn(OrdIx2) -> OrdIx2) -> Container {
+        Self::from_parts(self.charge, map_positions(&self.quarks, map)).into()
+    }
+
+    /// Quarks are only confined as a whole hadron.
+    fn without(&self, _pos: OrdIx2) -> Option<Container> {
+        None
+    }
+
+    fn charge(&self) -> i32 {
+        self.charge
+    }
+}
+
+impl Hadron {
+    /// Binds the quarks at `quarks` into a hadron. Only colour neutral
+    /// groupings of at least two quarks, each carrying a colour of its own
+    /// antiness, are confined into a hadron. The charge is the sum of the
+    /// quarks' charges on `board`.
+    pub fn new(board: &Board, quarks: SortedSet<OrdIx2>) -> Result<Self, ConfinementError> {
+        if quarks.len() < 2 {
+            return Err(ConfinementError::TooFewQuarks);
+        }
+
+        let mut charge = 0;
+        let mut colours = Vec::new();
+        for &pos in quarks.iter() {
+            let (quark_charge, anti, colour) = match board.particles().get(*pos) {
+                Some(Particle::UpQuark(q)) => (q.charge(), q.anti(), q.colour()),
+                Some(Particle::DownQuark(q)) => (q.charge(), q.anti(), q.colour()),
+                _ => return Err(ConfinementError::NotAQuark(pos)),
+            };
+            if colour.antiness() != Some(anti) {
+                return Err(ConfinementError::MismatchedColour(pos));
+            }
+            charge += quark_charge;
+            colours.push(colour);
+        }
+
+        if !Colour::is_neutral(colours.iter().copied()) {
+            return Err(ConfinementError::Coloured(Colour::mix(colours)));
+        }
+        Ok(Self::from_parts(charge, quarks))
+    }
+
+    fn from_parts(charge: i32, quarks: SortedSet<OrdIx2>) -> Self {
+        let contents = quarks
+            .iter()
+            .map(|&i| Component::Particle(i))
+            .collect::<Vec<_>>()
+            .into();
+        Self {
+            charge,
+            quarks,
+            contents,
+        }
+    }
+
+    pub fn quarks(&self) -> &SortedSet<OrdIx2> {
+        &self.quarks
+    }
+
+    /// Slides the hadron as one rigid body, see [`rigid_move`].
+    pub fn one_move(&self, board: &Board, direction: Direction) -> Option<Move> {
+        rigid_move(board, &self.clone().into(), direction)
+    }
+}
+
+/// Serialized form of [`Hadron`]; `contents` is rebuilt on load. The stored
+/// `charge` is only trusted until the board it belongs to rebinds the hadron
+/// with [`Hadron::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HadronRepr {
+    charge: i32,
+    quarks: Vec<OrdIx2>,
+}
+
+#[cfg(feature = "serde")]
+impl From<HadronRepr> for Hadron {
+    fn from(value: HadronRepr) -> Self {
+        Self::from_parts(value.charge, SortedSet::from_unsorted(value.quarks))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Hadron> for HadronRepr {
+    fn from(value: Hadron) -> Self {
+        Self {
+            charge: value.charge,
+            quarks: value.quarks.into_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+
+    use crate::particle::{DownQuark, UpQuark};
+    use crate::property::Antiness;
+
+    use super::*;
+
+    fn proton(board: &mut Board, colours: [Colour; 3]) -> Result<Hadron, ConfinementError> {
+        board.add_particle(UpQuark::new(Antiness::Ordinary, colours[0]), Ix2(1, 1));
+        board.add_particle(UpQuark::new(Antiness::Ordinary, colours[1]), Ix2(2, 1));
+        board.add_particle(DownQuark::new(Antiness::Ordinary, colours[2]), Ix2(1, 2));
+        Hadron::new(
+            board,
+            vec![Ix2(1, 1).into(), Ix2(2, 1).into(), Ix2(1, 2).into()].into(),
+        )
+    }
+
+    #[test]
+    fn hadron_confinement() {
+        let mut board = Board::new(5, 5, vec![].into());
+        let hadron = proton(&mut board, [Colour::Red, Colour::Green, Colour::Blue]).unwrap();
+        assert_eq!(hadron.charge(), 3);
+        assert_eq!(hadron.colour(), Colour::White);
+
+        assert_eq!(
+            proton(&mut board, [Colour::Red, Colour::Green, Colour::Green]),
+            Err(ConfinementError::Coloured(None))
+        );
+        assert_eq!(
+            proton(&mut board, [Colour::Red, Colour::Yellow, Colour::Blue]),
+            Err(ConfinementError::Coloured(Some(Colour::Red)))
+        );
+        assert_eq!(
+            proton(&mut board, [Colour::Red, Colour::Green, Colour::White]),
+            Err(ConfinementError::MismatchedColour(Ix2(1, 2).into()))
+        );
+        assert_eq!(
+            proton(&mut board, [Colour::Red, Colour::AntiRed, Colour::Blue]),
+            Err(ConfinementError::MismatchedColour(Ix2(2, 1).into()))
+        );
+
+        board.add_particle(UpQuark::new(Antiness::Ordinary, Colour::Red), Ix2(4, 4));
+        board.add_particle(UpQuark::new(Antiness::Anti, Colour::AntiRed), Ix2(4, 3));
+        let meson = Hadron::new(&board, vec![Ix2(4, 4).into(), Ix2(4, 3).into()].into());
+        assert_eq!(meson.map(|h| h.charge()), Ok(0));
+        board.add_particle(UpQuark::new(Antiness::Anti, Colour::Red), Ix2(4, 3));
+        assert_eq!(
+            Hadron::new(&board, vec![Ix2(4, 4).into(), Ix2(4, 3).into()].into()),
+            Err(ConfinementError::MismatchedColour(Ix2(4, 3).into()))
+        );
+        assert_eq!(
+            Hadron::new(&board, vec![Ix2(0, 0).into(), Ix2(4, 4).into()].into()),
+            Err(ConfinementError::NotAQuark(Ix2(0, 0).into()))
+        );
+
+        board.add_particle(UpQuark::new(Antiness::Ordinary, Colour::White), Ix2(0, 4));
+        assert_eq!(
+            Hadron::new(&board, vec![Ix2(0, 4).into()].into()),
+            Err(ConfinementError::TooFewQuarks)
+        );
+        assert_eq!(
+            Hadron::new(&board, vec![].into()),
+            Err(ConfinementError::TooFewQuarks)
+        );
+    }
+
+    #[test]
+    fn hadron_moves() {
+        let mut board = Board::new(5, 5, vec![].into());
+        let hadron = proton(&mut board, [Colour::Red, Colour::Green, Colour::Blue]).unwrap();
+        assert!(UpQuark::default().all_moves(&board, Ix2(1, 1)).is_empty());
+
+        board.add_container(hadron.clone().into());
+        let moves = board.all_moves();
+        assert_eq!(moves.len(), 4);
+
+        let right = board.applied(&hadron.one_move(&board, Direction::Right).unwrap());
+        assert_eq!(
+            right.particles().get([3, 1]),
+            Some(&UpQuark::new(Antiness::Ordinary, Colour::Red).into())
+        );
+        assert_eq!(
+            right.particles().get([4, 1]),
+            Some(&UpQuark::new(Antiness::Ordinary, Colour::Green).into())
+        );
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&DownQuark::new(Antiness::Ordinary, Colour::Blue).into())
+        );
+    }
+}
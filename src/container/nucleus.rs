@@ -1,12 +1,21 @@
 use sorted_vec::SortedSet;
 
+use crate::movement::Move;
 use crate::ordered::OrdIx2;
 use crate::property::Direction;
 use crate::{board::Board, property::Antiness};
 
-use super::{Component, ContainerTrait, Contents};
+use super::{
+    electric_directions, map_positions, rigid_move, translate_positions, Component, Container,
+    ContainerTrait, Contents,
+};
 
 #[derive(PartialEq, Eq, Clone, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "NucleusRepr", into = "NucleusRepr")
+)]
 pub struct NucleusParticle {
     antiness: Antiness,
     protons: SortedSet<OrdIx2>,
@@ -15,14 +24,50 @@ pub struct NucleusParticle {
 }
 
 impl ContainerTrait for NucleusParticle {
-    fn all_moves(&self, board: Board) -> Vec<Board> {
-        todo!()
+    fn all_moves(&self, board: &Board) -> Vec<Move> {
+        let container = Container::from(self.clone());
+        electric_directions(board, self)
+            .iter()
+            .filter_map(|&direction| rigid_move(board, &container, direction))
+            .collect()
     }
 
     fn contents(&self) -> &Contents {
         &self.contents
     }
 
+    fn translated(&self, board: &Board, direction: Direction, steps: usize) -> Container {
+        Self::new(
+            self.antiness,
+            translate_positions(board, &self.protons, direction, steps),
+            translate_positions(board, &self.neutrons, direction, steps),
+        )
+        .into()
+    }
+
+    fn mapped(&self, map: &dyn Fn(OrdIx2) -> OrdIx2) -> Container {
+        Self::new(
+            self.antiness,
+            map_positions(&self.protons, map),
+            map_positions(&self.neutrons, map),
+        )
+        .into()
+    }
+
+    /// A single nucleon left over is free again.
+    fn without(&self, pos: OrdIx2) -> Option<Container> {
+        let remove = |cells: &SortedSet<OrdIx2>| -> SortedSet<OrdIx2> {
+            cells
+                .iter()
+                .copied()
+                .filter(|&cell| cell != pos)
+                .collect::<Vec<_>>()
+                .into()
+        };
+        let nucleus = Self::new(self.antiness, remove(&self.protons), remove(&self.neutrons));
+        (nucleus.contents.len() >= 2).then(|| nucleus.into())
+    }
+
     fn charge(&self) -> i32 {
         let protons: i32 = self.protons.len().try_into().expect("too many protons");
         protons
@@ -50,7 +95,229 @@ impl NucleusParticle {
         }
     }
 
-    pub fn one_move(&self, direction: Direction) -> Board {
-        todo!()
+    pub fn antiness(&self) -> Antiness {
+        self.antiness
+    }
+
+    pub fn protons(&self) -> &SortedSet<OrdIx2> {
+        &self.protons
+    }
+
+    pub fn neutrons(&self) -> &SortedSet<OrdIx2> {
+        &self.neutrons
+    }
+
+    /// Slides the nucleus as one rigid body, see [`rigid_move`].
+    pub fn one_move(&self, board: &Board, direction: Direction) -> Option<Move> {
+        rigid_move(board, &self.clone().into(), direction)
+    }
+}
+
+/// Serialized form of [`NucleusParticle`]; `contents` is rebuilt on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct NucleusRepr {
+    antiness: Antiness,
+    protons: Vec<OrdIx2>,
+    neutrons: Vec<OrdIx2>,
+}
+
+#[cfg(feature = "serde")]
+impl From<NucleusRepr> for NucleusParticle {
+    fn from(value: NucleusRepr) -> Self {
+        Self::new(
+            value.antiness,
+            SortedSet::from_unsorted(value.protons),
+            SortedSet::from_unsorted(value.neutrons),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<NucleusParticle> for NucleusRepr {
+    fn from(value: NucleusParticle) -> Self {
+        Self {
+            antiness: value.antiness,
+            protons: value.protons.into_vec(),
+            neutrons: value.neutrons.into_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+
+    use crate::movement::Outcome;
+    use crate::obstacle::{block::Block, hole::Hole, Obstacle};
+    use crate::particle::{Electron, Neutron, Particle, Proton};
+
+    use super::*;
+
+    fn deuteron(board: &mut Board, proton: Ix2, neutron: Ix2) -> NucleusParticle {
+        board.add_particle(Proton::default(), proton);
+        board.add_particle(Neutron::default(), neutron);
+        let nucleus = NucleusParticle::new(
+            Antiness::Ordinary,
+            vec![proton.into()].into(),
+            vec![neutron.into()].into(),
+        );
+        board.add_container(nucleus.clone().into());
+        nucleus
+    }
+
+    #[test]
+    fn move_nucleus_edge() {
+        let mut board = Board::new(5, 7, vec![].into());
+        let nucleus = deuteron(&mut board, Ix2(1, 2), Ix2(2, 2));
+
+        let moves: Vec<_> = nucleus
+            .all_moves(&board)
+            .iter()
+            .map(|m| board.applied(m))
+            .collect();
+        assert_eq!(moves.len(), 4);
+
+        let right = &moves[1];
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&Proton::default().into())
+        );
+        assert_eq!(
+            right.particles().get([4, 2]),
+            Some(&Neutron::default().into())
+        );
+        assert_eq!(right.particles().get([1, 2]), Some(&Particle::default()));
+        assert_eq!(
+            right.top_container(&Component::Particle(Ix2(3, 2).into())),
+            Some(&Container::from(NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(3, 2).into()].into(),
+                vec![Ix2(4, 2).into()].into(),
+            )))
+        );
+        assert_eq!(
+            right.top_container(&Component::Particle(Ix2(1, 2).into())),
+            None
+        );
+
+        let up = &moves[2];
+        assert_eq!(up.particles().get([1, 0]), Some(&Proton::default().into()));
+        assert_eq!(up.particles().get([2, 0]), Some(&Neutron::default().into()));
+    }
+
+    #[test]
+    fn move_nucleus_collide() {
+        let mut board = Board::new(7, 7, vec![].into());
+        let nucleus = deuteron(&mut board, Ix2(1, 2), Ix2(2, 2));
+        board.add_particle(Electron::default(), Ix2(5, 2));
+        board.add_obstacle(Block, Ix2(1, 5));
+        board.add_particle(Electron::default(), Ix2(0, 2));
+
+        assert_eq!(nucleus.all_moves(&board).len(), 3);
+        assert_eq!(nucleus.one_move(&board, Direction::Left), None);
+
+        let right = board.applied(&nucleus.one_move(&board, Direction::Right).unwrap());
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&Proton::default().into())
+        );
+        assert_eq!(
+            right.particles().get([4, 2]),
+            Some(&Neutron::default().into())
+        );
+
+        let down = board.applied(&nucleus.one_move(&board, Direction::Down).unwrap());
+        assert_eq!(
+            down.particles().get([1, 4]),
+            Some(&Proton::default().into())
+        );
+        assert_eq!(
+            down.particles().get([2, 4]),
+            Some(&Neutron::default().into())
+        );
+    }
+
+    #[test]
+    fn move_nucleus_hole() {
+        let mut board = Board::new(5, 7, vec![].into());
+        let nucleus = deuteron(&mut board, Ix2(1, 2), Ix2(2, 2));
+        board.add_obstacle(Hole, Ix2(2, 5));
+
+        let down = board.applied(&nucleus.one_move(&board, Direction::Down).unwrap());
+        assert_eq!(down.particles().get([1, 2]), Some(&Particle::default()));
+        assert_eq!(down.particles().get([2, 2]), Some(&Particle::default()));
+        assert!(down.particles().iter().all(|p| p == &Particle::default()));
+        assert_eq!(
+            down.top_container(&Component::Particle(Ix2(1, 2).into())),
+            None
+        );
+    }
+
+    #[test]
+    fn move_nucleus_annihilate() {
+        let mut board = Board::new(7, 7, vec![].into());
+        let nucleus = deuteron(&mut board, Ix2(1, 2), Ix2(2, 2));
+        board.add_particle(Neutron::new(Antiness::Anti), Ix2(5, 2));
+        board.add_obstacle(Block, Ix2(5, 4));
+
+        let m = nucleus.one_move(&board, Direction::Right).unwrap();
+        assert_eq!(m.outcome(), Outcome::Annihilated(Ix2(5, 2).into()));
+        let right = board.applied(&m);
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&Proton::default().into())
+        );
+        assert_eq!(right.particles().get([4, 2]), Some(&Particle::default()));
+        assert_eq!(right.particles().get([5, 2]), Some(&Particle::default()));
+        assert_eq!(right.obstacles()[[5, 4]], Obstacle::default());
+        assert!(right.containers().is_empty());
+
+        board.add_particle(Neutron::default(), Ix2(1, 3));
+        board.remove_container(&nucleus.into());
+        let nucleus = NucleusParticle::new(
+            Antiness::Ordinary,
+            vec![Ix2(1, 2).into()].into(),
+            vec![Ix2(2, 2).into(), Ix2(1, 3).into()].into(),
+        );
+        board.add_container(nucleus.clone().into());
+        let right = board.applied(&nucleus.one_move(&board, Direction::Right).unwrap());
+        assert_eq!(
+            right.top_container(&Component::Particle(Ix2(3, 2).into())),
+            Some(&Container::from(NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(3, 2).into()].into(),
+                vec![Ix2(3, 3).into()].into(),
+            )))
+        );
+        assert_eq!(
+            right.particles().get([3, 3]),
+            Some(&Neutron::default().into())
+        );
+    }
+
+    #[test]
+    fn move_nucleus_electric() {
+        let mut board = Board::new(7, 7, vec![].into());
+        let nucleus = deuteron(&mut board, Ix2(1, 2), Ix2(2, 2));
+
+        assert_eq!(nucleus.all_moves(&board).len(), 4);
+
+        board.add_particle(Electron::default(), Ix2(5, 2));
+        let moves = nucleus.all_moves(&board);
+        assert_eq!(moves.len(), 3);
+        assert_eq!(
+            board.applied(&moves[0]).particles().get([3, 2]),
+            Some(&Proton::default().into())
+        );
+        assert_eq!(
+            board.applied(&moves[0]).particles().get([4, 2]),
+            Some(&Neutron::default().into())
+        );
+
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(1, 6));
+        board.add_particle(Electron::default(), Ix2(2, 6));
+        let moves = nucleus.all_moves(&board);
+        assert_eq!(moves.len(), 3);
     }
 }
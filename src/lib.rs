@@ -2,6 +2,7 @@
 
 pub mod board;
 pub mod container;
+pub mod movement;
 pub mod obstacle;
 pub mod ordered;
 pub mod particle;
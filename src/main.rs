@@ -3,7 +3,7 @@ use std::time::Instant;
 use higgs::board::Board;
 use higgs::obstacle::{block::Block, hole::Hole};
 use higgs::particle::{Electron, Neutron, Tau};
-use higgs::solver::Solver;
+use higgs::solver::{Solver, TooFewParticles, UnreachableGoals};
 use ndarray::Ix2;
 use slotmap::new_key_type;
 
@@ -24,19 +24,19 @@ fn main() {
     board.add_obstacle(Hole, Ix2(2, 7));
     board.add_obstacle(Hole, Ix2(5, 7));
 
-    let mut solver: Solver<SolverKey> = Solver::new(board);
+    let mut solver: Solver<SolverKey> = Solver::new(board)
+        .with_pruner(TooFewParticles)
+        .with_pruner(UnreachableGoals);
 
     let now = Instant::now();
-    let mut solutions = &solver.verbose_solve();
+    let solutions = solver.verbose_solve();
     let elapsed = now.elapsed();
     println!("Solve time: {:.2?}", elapsed);
-    let mut steps = Vec::new();
-    while let Some(&step) = solutions.first() {
-        steps.push(step);
-        solutions = solver.tree().parents_of(step).unwrap()
-    }
-
-    for &step in steps.iter().rev() {
-        solver.tree().get_value(step).unwrap().display_particles();
+    if let Some(&solved) = solutions.first() {
+        let solution = solver.solution(solved);
+        for board in solution.boards() {
+            board.display_particles();
+        }
+        print!("{solution}");
     }
 }
@@ -0,0 +1,333 @@
+//! Moves as values that can be applied to and undone on a [`Board`].
+//!
+//! A [`Move`] keeps what happened for describing it and the cell by cell
+//! changes it makes, each with the value it replaces, so that
+//! [`Board::apply`] and [`Board::undo`] never need to clone the board.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use ndarray::Ix2;
+
+use crate::board::Board;
+use crate::container::{contents_positions, Component, Container};
+use crate::obstacle::Obstacle;
+use crate::ordered::OrdIx2;
+use crate::particle::{Particle, ParticleTrait};
+use crate::property::Direction;
+
+/// Where a moving particle or container ended up.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    /// Stopped at the given position, which is the origin itself if it only
+    /// pushed another particle. For a container this is its first cell.
+    SlidTo(OrdIx2),
+    /// Annihilated with the antiparticle at the given position.
+    Annihilated(OrdIx2),
+    /// Fell into the hole at the given position.
+    FellInto(OrdIx2),
+}
+
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub(crate) enum Change {
+    Particle {
+        pos: OrdIx2,
+        before: Particle,
+        after: Particle,
+    },
+    Lifetime {
+        pos: OrdIx2,
+        before: Option<u32>,
+        after: Option<u32>,
+    },
+    Obstacle {
+        pos: OrdIx2,
+        before: Obstacle,
+        after: Obstacle,
+    },
+    Container {
+        before: Option<Container>,
+        after: Option<Container>,
+    },
+}
+
+/// One global move: a free particle or a top-level container sliding in a
+/// direction, together with everything it changes on the board it was
+/// generated for.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Move {
+    origin: Component,
+    direction: Direction,
+    outcome: Outcome,
+    pushed: Option<(OrdIx2, Outcome)>,
+    changes: Vec<Change>,
+}
+
+impl Move {
+    pub fn origin(&self) -> &Component {
+        &self.origin
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+
+    /// The particle shoved by this move and where it ended up, if any.
+    pub fn pushed(&self) -> Option<(OrdIx2, Outcome)> {
+        self.pushed
+    }
+
+    pub(crate) fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Appends the countdown of every lifetime as it is after this move, so
+    /// that particles decay as part of the move.
+    pub(crate) fn tick(mut self, board: &Board) -> Self {
+        let mut changed = BTreeMap::new();
+        let mut particles = BTreeMap::new();
+        for change in &self.changes {
+            match *change {
+                Change::Lifetime { pos, after, .. } => {
+                    changed.insert(pos, after);
+                }
+                Change::Particle { pos, after, .. } => {
+                    particles.insert(pos, after);
+                }
+                _ => (),
+            }
+        }
+
+        let unchanged = board
+            .lifetimes()
+            .iter()
+            .filter(|(pos, _lifetime)| !changed.contains_key(*pos))
+            .map(|(&pos, &lifetime)| (pos, lifetime));
+        let lifetimes = changed
+            .iter()
+            .filter_map(|(&pos, &lifetime)| Some((pos, lifetime?)));
+        for (pos, lifetime) in unchanged.chain(lifetimes) {
+            let after = lifetime.checked_sub(1).filter(|&lifetime| lifetime > 0);
+            self.changes.push(Change::Lifetime {
+                pos,
+                before: Some(lifetime),
+                after,
+            });
+            if after.is_some() {
+                continue;
+            }
+            let particle = particles
+                .get(&pos)
+                .copied()
+                .unwrap_or_else(|| board.particles()[*pos]);
+            if let Some(product) = particle.decay() {
+                self.changes.push(Change::Particle {
+                    pos,
+                    before: particle,
+                    after: product,
+                });
+                if product.lifetime() > 0 {
+                    self.changes.push(Change::Lifetime {
+                        pos,
+                        before: None,
+                        after: Some(product.lifetime()),
+                    });
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let position = |pos: OrdIx2| format!("({}, {})", pos[0], pos[1]);
+        let outcome = |outcome: Outcome| match outcome {
+            Outcome::SlidTo(pos) => format!("slid to {}", position(pos)),
+            Outcome::Annihilated(pos) => format!("annihilated at {}", position(pos)),
+            Outcome::FellInto(pos) => format!("fell into the hole at {}", position(pos)),
+        };
+        let direction = match self.direction {
+            Direction::Right => "right",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Up => "up",
+        };
+
+        match &self.origin {
+            Component::Particle(pos) => write!(f, "{}", position(*pos))?,
+            Component::Container(contents) => write!(
+                f,
+                "container at {}",
+                position(contents_positions(contents)[0])
+            )?,
+        };
+        write!(f, " {direction}: {}", outcome(self.outcome))?;
+        if let Some((pos, pushed)) = self.pushed {
+            write!(f, ", pushing {} which {}", position(pos), outcome(pushed))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Records the changes of a move against the unchanged `board`. Every cell is
+/// expected to be set at most once per move.
+pub(crate) struct MoveBuilder<'a> {
+    board: &'a Board,
+    pushed: Option<(OrdIx2, Outcome)>,
+    changes: Vec<Change>,
+}
+
+impl<'a> MoveBuilder<'a> {
+    pub fn new(board: &'a Board) -> Self {
+        Self {
+            board,
+            pushed: None,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn set_particle(&mut self, pos: Ix2, particle: Particle, lifetime: Option<u32>) {
+        let before = self.board.particles()[pos];
+        if before != particle {
+            self.changes.push(Change::Particle {
+                pos: pos.into(),
+                before,
+                after: particle,
+            });
+        }
+        let before = self.board.lifetime(pos);
+        if before != lifetime {
+            self.changes.push(Change::Lifetime {
+                pos: pos.into(),
+                before,
+                after: lifetime,
+            });
+        }
+    }
+
+    pub fn remove_particle(&mut self, pos: Ix2) {
+        self.set_particle(pos, Particle::default(), None);
+    }
+
+    pub fn move_particle(&mut self, from_pos: Ix2, to_pos: Ix2) {
+        self.set_particle(
+            to_pos,
+            self.board.particles()[from_pos],
+            self.board.lifetime(from_pos),
+        );
+        self.remove_particle(from_pos);
+    }
+
+    pub fn annihilate(&mut self, pos: Ix2, strength: usize) {
+        for pos in self.board.annihilation_area(pos, strength) {
+            let before = self.board.obstacles()[pos];
+            if !matches!(before, Obstacle::Empty(_)) {
+                self.changes.push(Change::Obstacle {
+                    pos: pos.into(),
+                    before,
+                    after: Obstacle::default(),
+                });
+            }
+        }
+    }
+
+    pub fn replace_container(&mut self, before: Option<Container>, after: Option<Container>) {
+        self.changes.push(Change::Container { before, after });
+    }
+
+    /// Takes over the changes of a move made by a pushed particle.
+    pub fn push(&mut self, pushed: Move) {
+        if let Component::Particle(pos) = pushed.origin {
+            self.pushed = Some((pos, pushed.outcome));
+        }
+        self.changes.extend(pushed.changes);
+    }
+
+    pub fn finish(self, origin: Component, direction: Direction, outcome: Outcome) -> Move {
+        Move {
+            origin,
+            direction,
+            outcome,
+            pushed: self.pushed,
+            changes: self.changes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::NucleusParticle;
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Muon, Neutron, Proton};
+    use crate::property::Antiness;
+
+    use super::*;
+
+    #[test]
+    fn apply_undo_round_trip() {
+        let mut board = Board::new(7, 7, vec![Ix2(6, 6).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 0));
+        board.add_particle(Muon::default(), Ix2(3, 0));
+        board.set_lifetime(Ix2(3, 0), 1);
+        board.add_particle(Electron::default(), Ix2(0, 3));
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(0, 6));
+        board.add_obstacle(Block, Ix2(1, 5));
+        board.add_obstacle(Hole, Ix2(6, 3));
+        board.add_particle(Proton::default(), Ix2(3, 4));
+        board.add_particle(Neutron::default(), Ix2(4, 4));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(3, 4).into()].into(),
+                vec![Ix2(4, 4).into()].into(),
+            )
+            .into(),
+        );
+
+        let moves = board.all_moves();
+        assert!(moves
+            .iter()
+            .any(|m| matches!(m.outcome(), Outcome::Annihilated(_))));
+        assert!(moves.iter().any(|m| m.pushed().is_some()));
+        for m in &moves {
+            let mut new_board = board.clone();
+            new_board.apply(m);
+            assert_ne!(new_board, board, "{m}");
+            assert!(
+                !new_board
+                    .particles()
+                    .iter()
+                    .any(|&p| p == Muon::default().into()),
+                "{m}"
+            );
+            new_board.undo(m);
+            assert_eq!(new_board, board, "{m}");
+        }
+
+        let annihilated = moves
+            .iter()
+            .find(|m| {
+                m.origin() == &Component::Particle(Ix2(0, 3).into())
+                    && m.direction() == Direction::Down
+            })
+            .unwrap();
+        assert_eq!(
+            annihilated.outcome(),
+            Outcome::Annihilated(Ix2(0, 6).into())
+        );
+        let new_board = board.applied(annihilated);
+        assert_eq!(new_board.obstacles()[[1, 5]], Obstacle::default());
+        assert_eq!(
+            annihilated.to_string(),
+            "(0, 3) down: annihilated at (0, 6)"
+        );
+    }
+}
@@ -10,6 +10,7 @@ pub trait ObstacleTrait {}
 
 #[enum_dispatch(ObstacleTrait)]
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Obstacle {
     Empty,
     Block,
@@ -23,6 +24,7 @@ impl Default for Obstacle {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Empty;
 
 impl ObstacleTrait for Empty {}
@@ -1,6 +1,7 @@
 use super::ObstacleTrait;
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hole;
 
 impl ObstacleTrait for Hole {}
@@ -41,3 +41,18 @@ impl PartialOrd for OrdIx2 {
         Some(self.cmp(other))
     }
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for OrdIx2 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self[0], self[1]].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OrdIx2 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y] = <[usize; 2]>::deserialize(deserializer)?;
+        Ok(OrdIx2(UnOrdIx2(x, y)))
+    }
+}
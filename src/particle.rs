@@ -2,30 +2,55 @@ use enum_dispatch::enum_dispatch;
 use ndarray::Ix2;
 
 use crate::board::Board;
+use crate::container::Component;
+use crate::movement::{Move, MoveBuilder, Outcome};
+use crate::obstacle::Obstacle;
+use crate::property::{AntiTrait, Direction, Mass, MassTrait};
 
-pub use self::{electron::Electron, muon::Muon, neutron::Neutron, tau::Tau};
+pub use self::{
+    down_quark::DownQuark, electron::Electron, muon::Muon, neutron::Neutron, proton::Proton,
+    tau::Tau, up_quark::UpQuark,
+};
 
+mod down_quark;
 mod electron;
 mod muon;
 mod neutron;
+mod proton;
 mod tau;
+mod up_quark;
 
 #[enum_dispatch]
 pub trait ParticleTrait {
     fn charge(&self) -> i32 {
         0
     }
-    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Board>;
+    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Move>;
+
+    /// What the particle turns into when its lifetime runs out, if anything.
+    fn decay(&self) -> Option<Particle> {
+        None
+    }
+
+    /// Global moves the particle lasts when a decay produces it, or zero if
+    /// it is stable.
+    fn lifetime(&self) -> u32 {
+        0
+    }
 }
 
 #[enum_dispatch(ParticleTrait)]
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Particle {
     Empty,
     Electron,
     Muon,
     Tau,
     Neutron,
+    Proton,
+    UpQuark,
+    DownQuark,
 }
 
 impl Default for Particle {
@@ -35,10 +60,252 @@ impl Default for Particle {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Empty;
 
 impl ParticleTrait for Empty {
-    fn all_moves(&self, _board: &Board, _pos: Ix2) -> Vec<Board> {
+    fn all_moves(&self, _board: &Board, _pos: Ix2) -> Vec<Move> {
         unimplemented!()
     }
 }
+
+impl MassTrait for Empty {
+    fn mass(&self) -> Mass {
+        Mass::Motionless
+    }
+}
+
+impl MassTrait for Particle {
+    fn mass(&self) -> Mass {
+        match self {
+            Particle::Empty(p) => p.mass(),
+            Particle::Electron(p) => p.mass(),
+            Particle::Muon(p) => p.mass(),
+            Particle::Tau(p) => p.mass(),
+            Particle::Neutron(p) => p.mass(),
+            Particle::Proton(p) => p.mass(),
+            Particle::UpQuark(p) => p.mass(),
+            Particle::DownQuark(p) => p.mass(),
+        }
+    }
+}
+
+impl Particle {
+    /// Whether `self` and `other` are a particle and its antiparticle.
+    pub fn annihilates(&self, other: &Particle) -> bool {
+        match (self, other) {
+            (Particle::Electron(a), Particle::Electron(b)) => a.anti() != b.anti(),
+            (Particle::Muon(a), Particle::Muon(b)) => a.anti() != b.anti(),
+            (Particle::Tau(a), Particle::Tau(b)) => a.anti() != b.anti(),
+            (Particle::Neutron(a), Particle::Neutron(b)) => a.anti() != b.anti(),
+            (Particle::Proton(a), Particle::Proton(b)) => a.anti() != b.anti(),
+            _ => false,
+        }
+    }
+}
+
+/// Slides the free particle at `pos` in `direction` until it stops.
+///
+/// The particle stops in front of a block, the edge of the board or another
+/// particle, falls into a hole and annihilates with its antiparticle. Its
+/// [`Mass`] changes how it meets other particles: a motionless particle never
+/// moves, a massless one passes through them and a heavy one stops and pushes
+/// a lighter one on in the same direction. Particles bound in a container are
+/// never annihilated or pushed, and neither are motionless ones.
+pub fn slide(board: &Board, pos: Ix2, direction: Direction) -> Option<Move> {
+    let mass = board.particles().get(pos)?.mass();
+    slide_with_mass(board, pos, direction, mass)
+}
+
+/// [`slide`] with the particle at `pos` taken to have `mass`, which lets the
+/// tests cover masses no particle has.
+fn slide_with_mass(board: &Board, pos: Ix2, direction: Direction, mass: Mass) -> Option<Move> {
+    let particle = *board.particles().get(pos)?;
+    if mass == Mass::Motionless {
+        return None;
+    }
+
+    let origin = Component::Particle(pos.into());
+    let mut builder = MoveBuilder::new(board);
+    let mut previous = pos;
+    let mut last_free = pos;
+    while let Some(next) = board.move_direction(direction, previous) {
+        let other = board.particles().get(next).unwrap();
+        if !matches!(other, Particle::Empty(_)) {
+            let bound = board
+                .top_container(&Component::Particle(next.into()))
+                .is_some();
+            if !bound && particle.annihilates(other) {
+                builder.remove_particle(pos);
+                builder.remove_particle(next);
+                builder.annihilate(next, 3);
+                return Some(builder.finish(origin, direction, Outcome::Annihilated(next.into())));
+            }
+            if mass != Mass::Massless {
+                let pushed = match !bound && mass.pushes(other.mass()) {
+                    true => slide(board, next, direction),
+                    false => None,
+                };
+                if pushed.is_none() && pos == last_free {
+                    return None;
+                }
+                if let Some(pushed) = pushed {
+                    builder.push(pushed);
+                }
+                if pos != last_free {
+                    builder.move_particle(pos, last_free);
+                }
+                return Some(builder.finish(origin, direction, Outcome::SlidTo(last_free.into())));
+            }
+        }
+        match board.obstacles().get(next).unwrap() {
+            Obstacle::Empty(_) => (),
+            Obstacle::Block(_) => break,
+            Obstacle::Hole(_) => {
+                builder.remove_particle(pos);
+                return Some(builder.finish(origin, direction, Outcome::FellInto(next.into())));
+            }
+        };
+        if matches!(other, Particle::Empty(_)) {
+            last_free = next;
+        }
+        previous = next
+    }
+
+    if pos == last_free {
+        return None;
+    }
+    builder.move_particle(pos, last_free);
+    Some(builder.finish(origin, direction, Outcome::SlidTo(last_free.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::NucleusParticle;
+    use crate::property::Antiness;
+
+    use super::*;
+
+    #[test]
+    fn slide_heavy_pushes_lighter() {
+        let mut board = Board::new(7, 5, vec![].into());
+        board.add_particle(Tau::default(), Ix2(0, 2));
+        board.add_particle(Electron::default(), Ix2(3, 2));
+
+        let right = board.applied(&slide(&board, Ix2(0, 2), Direction::Right).unwrap());
+        assert_eq!(right.particles().get([2, 2]), Some(&Tau::default().into()));
+        assert_eq!(
+            right.particles().get([6, 2]),
+            Some(&Electron::default().into())
+        );
+
+        let left = board.applied(&slide(&board, Ix2(3, 2), Direction::Left).unwrap());
+        assert_eq!(
+            left.particles().get([1, 2]),
+            Some(&Electron::default().into())
+        );
+        assert_eq!(left.particles().get([0, 2]), Some(&Tau::default().into()));
+
+        board.add_particle(Electron::default(), Ix2(1, 2));
+        let pushed_in_place = board.applied(&slide(&board, Ix2(0, 2), Direction::Right).unwrap());
+        assert_eq!(
+            pushed_in_place.particles().get([0, 2]),
+            Some(&Tau::default().into())
+        );
+        assert_eq!(
+            pushed_in_place.particles().get([2, 2]),
+            Some(&Electron::default().into())
+        );
+    }
+
+    #[test]
+    fn slide_bound_particles_are_walls() {
+        let mut board = Board::new(7, 5, vec![].into());
+        board.add_particle(Neutron::new(Antiness::Anti), Ix2(0, 2));
+        board.add_particle(Neutron::default(), Ix2(4, 2));
+        board.add_particle(Proton::default(), Ix2(5, 2));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![Ix2(5, 2).into()].into(),
+                vec![Ix2(4, 2).into()].into(),
+            )
+            .into(),
+        );
+
+        let right = board.applied(&slide(&board, Ix2(0, 2), Direction::Right).unwrap());
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&Neutron::new(Antiness::Anti).into())
+        );
+        assert_eq!(
+            right.particles().get([4, 2]),
+            Some(&Neutron::default().into())
+        );
+    }
+
+    #[test]
+    fn slide_massless_passes_through() {
+        let mut board = Board::new(7, 5, vec![].into());
+        board.add_particle(Electron::default(), Ix2(0, 2));
+        board.add_particle(Neutron::default(), Ix2(2, 2));
+        board.add_particle(Neutron::default(), Ix2(3, 2));
+
+        let m = slide_with_mass(&board, Ix2(0, 2), Direction::Right, Mass::Massless).unwrap();
+        let right = board.applied(&m);
+        assert_eq!(
+            right.particles().get([6, 2]),
+            Some(&Electron::default().into())
+        );
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&Neutron::default().into())
+        );
+
+        board.add_particle(Neutron::default(), Ix2(6, 2));
+        let m = slide_with_mass(&board, Ix2(0, 2), Direction::Right, Mass::Massless).unwrap();
+        assert_eq!(
+            board.applied(&m).particles().get([5, 2]),
+            Some(&Electron::default().into())
+        );
+    }
+
+    #[test]
+    fn slide_motionless_never_moves() {
+        let mut board = Board::new(7, 5, vec![].into());
+        board.add_particle(Electron::default(), Ix2(3, 2));
+        for direction in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            assert!(slide(&board, Ix2(3, 2), direction).is_some());
+            assert_eq!(
+                slide_with_mass(&board, Ix2(3, 2), direction, Mass::Motionless),
+                None
+            );
+        }
+
+        board.add_particle(UpQuark::default(), Ix2(0, 2));
+        assert_eq!(UpQuark::default().mass(), Mass::Motionless);
+        assert_eq!(slide(&board, Ix2(0, 2), Direction::Right), None);
+    }
+
+    #[test]
+    fn slide_quarks_are_walls() {
+        let mut board = Board::new(7, 5, vec![].into());
+        board.add_particle(Tau::default(), Ix2(0, 2));
+        board.add_particle(UpQuark::default(), Ix2(3, 2));
+
+        let right = board.applied(&slide(&board, Ix2(0, 2), Direction::Right).unwrap());
+        assert_eq!(right.particles().get([2, 2]), Some(&Tau::default().into()));
+        assert_eq!(
+            right.particles().get([3, 2]),
+            Some(&UpQuark::default().into())
+        );
+
+        board.add_particle(DownQuark::default(), Ix2(1, 2));
+        assert_eq!(slide(&board, Ix2(0, 2), Direction::Right), None);
+    }
+}
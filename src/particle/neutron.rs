@@ -1,24 +1,42 @@
 use ndarray::Ix2;
 
 use crate::board::Board;
-use crate::obstacle::Obstacle;
-use crate::particle::Particle;
-use crate::property::{Antiness, Direction};
+use crate::movement::Move;
+use crate::property::{AntiTrait, Antiness, Direction, Mass, MassTrait};
 
-use super::ParticleTrait;
+use super::{slide, ParticleTrait};
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Neutron {
     anti: Antiness,
 }
 
+impl Neutron {
+    pub fn new(anti: Antiness) -> Self {
+        Self { anti }
+    }
+}
+
+impl AntiTrait for Neutron {
+    fn anti(&self) -> Antiness {
+        self.anti
+    }
+}
+
+impl MassTrait for Neutron {
+    fn mass(&self) -> Mass {
+        Mass::Heavy
+    }
+}
+
 impl ParticleTrait for Neutron {
-    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Board> {
+    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Move> {
         vec![
-            self.one_move(board, pos, Direction::Right),
-            self.one_move(board, pos, Direction::Down),
-            self.one_move(board, pos, Direction::Left),
-            self.one_move(board, pos, Direction::Up),
+            slide(board, pos, Direction::Right),
+            slide(board, pos, Direction::Down),
+            slide(board, pos, Direction::Left),
+            slide(board, pos, Direction::Up),
         ]
         .into_iter()
         .flatten()
@@ -26,74 +44,6 @@ impl ParticleTrait for Neutron {
     }
 }
 
-impl Neutron {
-    fn one_move(&self, board: &Board, pos: Ix2, direction: Direction) -> Option<Board> {
-        let move_fn = |i| board.move_direction(direction, i);
-        let next = move_fn(pos)?;
-        match board.particles().get(next).unwrap() {
-            Particle::Empty(_) => (),
-            Particle::Neutron(n) => match n.anti == self.anti {
-                true => return None,
-                false => {
-                    let mut ret_board = board.clone();
-                    ret_board.remove_particle(pos);
-                    ret_board.remove_particle(next);
-                    ret_board.annihilate(next, 3);
-                    return Some(ret_board);
-                }
-            },
-            _ => return None,
-        };
-        match board.obstacles().get(next).unwrap() {
-            Obstacle::Empty(_) => (),
-            Obstacle::Block(_) => return None,
-            Obstacle::Hole(_) => {
-                let mut ret_board = board.clone();
-                ret_board.remove_particle(pos);
-                return Some(ret_board);
-            }
-        };
-
-        let mut previous = next;
-        let mut ret_board = board.clone();
-        while let Some(next) = move_fn(previous) {
-            match board.particles().get(next).unwrap() {
-                Particle::Empty(_) => (),
-                Particle::Neutron(n) => match n.anti == self.anti {
-                    true => {
-                        ret_board.move_particle(pos, previous);
-                        return Some(ret_board);
-                    }
-                    false => {
-                        ret_board.remove_particle(pos);
-                        ret_board.remove_particle(next);
-                        ret_board.annihilate(next, 3);
-                        return Some(ret_board);
-                    }
-                },
-                _ => {
-                    ret_board.move_particle(pos, previous);
-                    return Some(ret_board);
-                }
-            };
-            match board.obstacles().get(next).unwrap() {
-                Obstacle::Empty(_) => (),
-                Obstacle::Block(_) => {
-                    ret_board.move_particle(pos, previous);
-                    return Some(ret_board);
-                }
-                Obstacle::Hole(_) => {
-                    ret_board.remove_particle(pos);
-                    return Some(ret_board);
-                }
-            };
-            previous = next
-        }
-        ret_board.move_particle(pos, previous)?;
-        Some(ret_board)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::particle::Empty;
@@ -117,7 +67,10 @@ mod tests {
             .particles()
             .get(start_index)
             .unwrap()
-            .all_moves(&board, start_index);
+            .all_moves(&board, start_index)
+            .iter()
+            .map(|m| board.applied(m))
+            .collect::<Vec<_>>();
         for new_b in &new_boards {
             assert_eq!(new_b.particles().get(start_index), Some(&Empty.into()));
         }
@@ -202,7 +155,10 @@ mod tests {
             .particles()
             .get(start_index)
             .unwrap()
-            .all_moves(&board, start_index);
+            .all_moves(&board, start_index)
+            .iter()
+            .map(|m| board.applied(m))
+            .collect::<Vec<_>>();
 
         assert_eq!(new_boards.len(), 3);
 
@@ -249,7 +205,10 @@ mod tests {
             .particles()
             .get(start_index)
             .unwrap()
-            .all_moves(&board, start_index);
+            .all_moves(&board, start_index)
+            .iter()
+            .map(|m| board.applied(m))
+            .collect::<Vec<_>>();
 
         assert_eq!(new_boards.len(), 3);
 
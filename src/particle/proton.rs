@@ -0,0 +1,108 @@
+use ndarray::Ix2;
+
+use crate::board::Board;
+use crate::movement::Move;
+use crate::property::{AntiTrait, Antiness, Direction, Mass, MassTrait};
+
+use super::{slide, ParticleTrait};
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Proton {
+    anti: Antiness,
+}
+
+impl Proton {
+    pub fn new(anti: Antiness) -> Self {
+        Self { anti }
+    }
+}
+
+impl AntiTrait for Proton {
+    fn anti(&self) -> Antiness {
+        self.anti
+    }
+}
+
+impl MassTrait for Proton {
+    fn mass(&self) -> Mass {
+        Mass::Heavy
+    }
+}
+
+impl ParticleTrait for Proton {
+    fn charge(&self) -> i32 {
+        match self.anti() {
+            Antiness::Ordinary => 3,
+            Antiness::Anti => -3,
+        }
+    }
+
+    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Move> {
+        self.check_electric(board, pos)
+            .iter()
+            .filter_map(|&direction| slide(board, pos, direction))
+            .collect()
+    }
+}
+
+impl Proton {
+    fn check_electric(&self, board: &Board, pos: Ix2) -> Vec<Direction> {
+        let mut ret_directions: Vec<Direction> = Vec::new();
+
+        let x = self.charge()
+            * (board.find_charge_single(pos, Direction::Right)
+                - board.find_charge_single(pos, Direction::Left));
+        if x >= 0 {
+            ret_directions.push(Direction::Left)
+        }
+        if x <= 0 {
+            ret_directions.push(Direction::Right)
+        }
+
+        let y = self.charge()
+            * (board.find_charge_single(pos, Direction::Down)
+                - board.find_charge_single(pos, Direction::Up));
+        if y >= 0 {
+            ret_directions.push(Direction::Up)
+        }
+        if y <= 0 {
+            ret_directions.push(Direction::Down)
+        }
+
+        ret_directions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::obstacle::{block::Block, Obstacle};
+    use crate::particle::{Electron, Empty};
+
+    use super::*;
+
+    #[test]
+    fn move_proton_electric() {
+        let mut board = Board::new(7, 7, vec![].into());
+        board.add_particle(Proton::default(), Ix2(3, 3));
+        board.add_particle(Electron::default(), Ix2(3, 0));
+
+        let moves = Proton::default().all_moves(&board, Ix2(3, 3));
+        assert_eq!(moves.len(), 3);
+        assert!(moves
+            .iter()
+            .all(|m| board.applied(m).particles().get([3, 6]) == Some(&Empty.into())));
+    }
+
+    #[test]
+    fn move_proton_annihilate() {
+        let mut board = Board::new(7, 7, vec![].into());
+        board.add_particle(Proton::default(), Ix2(1, 3));
+        board.add_particle(Proton::new(Antiness::Anti), Ix2(5, 3));
+        board.add_obstacle(Block, Ix2(5, 5));
+
+        let right = board.applied(&slide(&board, Ix2(1, 3), Direction::Right).unwrap());
+        assert!(right.particles().iter().all(|p| p == &Empty.into()));
+        assert_eq!(right.obstacles().get([5, 5]), Some(&Obstacle::default()));
+    }
+}
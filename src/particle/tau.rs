@@ -1,12 +1,13 @@
 use ndarray::Ix2;
 
 use crate::board::Board;
-use crate::obstacle::Obstacle;
-use crate::property::{AntiTrait, Antiness, Direction};
+use crate::movement::Move;
+use crate::property::{AntiTrait, Antiness, Direction, Mass, MassTrait};
 
-use super::{Particle, ParticleTrait};
+use super::{slide, Muon, Particle, ParticleTrait};
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tau {
     anti: Antiness,
 }
@@ -23,6 +24,12 @@ impl AntiTrait for Tau {
     }
 }
 
+impl MassTrait for Tau {
+    fn mass(&self) -> Mass {
+        Mass::Heavy
+    }
+}
+
 impl ParticleTrait for Tau {
     fn charge(&self) -> i32 {
         match self.anti() {
@@ -31,12 +38,16 @@ impl ParticleTrait for Tau {
         }
     }
 
-    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Board> {
+    fn all_moves(&self, board: &Board, pos: Ix2) -> Vec<Move> {
         self.check_electric(board, pos)
             .iter()
-            .filter_map(|&direction| self.one_move(board, pos, direction))
+            .filter_map(|&direction| slide(board, pos, direction))
             .collect()
     }
+
+    fn decay(&self) -> Option<Particle> {
+        Some(Muon::new(self.anti).into())
+    }
 }
 
 impl Tau {
@@ -65,70 +76,4 @@ impl Tau {
 
         ret_directions
     }
-
-    fn one_move(&self, board: &Board, pos: Ix2, direction: Direction) -> Option<Board> {
-        let move_fn = |i| board.move_direction(direction, i);
-        let next = move_fn(pos)?;
-        match board.particles().get(next).unwrap() {
-            Particle::Empty(_) => (),
-            Particle::Tau(t) => match t.anti == self.anti {
-                true => return None,
-                false => {
-                    let mut ret_board = board.clone();
-                    ret_board.remove_particle(pos);
-                    ret_board.remove_particle(next);
-                    ret_board.annihilate(next, 3);
-                    return Some(ret_board);
-                }
-            },
-            _ => return None,
-        };
-        match board.obstacles().get(next).unwrap() {
-            Obstacle::Empty(_) => (),
-            Obstacle::Block(_) => return None,
-            Obstacle::Hole(_) => {
-                let mut ret_board = board.clone();
-                ret_board.remove_particle(pos);
-                return Some(ret_board);
-            }
-        };
-
-        let mut previous = next;
-        let mut ret_board = board.clone();
-        while let Some(next) = move_fn(previous) {
-            match board.particles().get(next).unwrap() {
-                Particle::Empty(_) => (),
-                Particle::Tau(t) => match t.anti == self.anti {
-                    true => {
-                        ret_board.move_particle(pos, previous);
-                        return Some(ret_board);
-                    }
-                    false => {
-                        ret_board.remove_particle(pos);
-                        ret_board.remove_particle(next);
-                        ret_board.annihilate(next, 3);
-                        return Some(ret_board);
-                    }
-                },
-                _ => {
-                    ret_board.move_particle(pos, previous);
-                    return Some(ret_board);
-                }
-            };
-            match board.obstacles().get(next).unwrap() {
-                Obstacle::Empty(_) => (),
-                Obstacle::Block(_) => {
-                    ret_board.move_particle(pos, previous);
-                    return Some(ret_board);
-                }
-                Obstacle::Hole(_) => {
-                    ret_board.remove_particle(pos);
-                    return Some(ret_board);
-                }
-            };
-            previous = next
-        }
-        ret_board.move_particle(pos, previous)?;
-        Some(ret_board)
-    }
 }
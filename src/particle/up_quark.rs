@@ -0,0 +1,53 @@
+use ndarray::Ix2;
+
+use crate::board::Board;
+use crate::movement::Move;
+use crate::property::{AntiTrait, Antiness, Colour, ColourTrait, Mass, MassTrait};
+
+use super::ParticleTrait;
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpQuark {
+    anti: Antiness,
+    colour: Colour,
+}
+
+impl UpQuark {
+    pub fn new(anti: Antiness, colour: Colour) -> Self {
+        Self { anti, colour }
+    }
+}
+
+impl AntiTrait for UpQuark {
+    fn anti(&self) -> Antiness {
+        self.anti
+    }
+}
+
+impl ColourTrait for UpQuark {
+    fn colour(&self) -> Colour {
+        self.colour
+    }
+}
+
+impl MassTrait for UpQuark {
+    /// Never slides on its own, so nothing pushes it out of its hadron.
+    fn mass(&self) -> Mass {
+        Mass::Motionless
+    }
+}
+
+impl ParticleTrait for UpQuark {
+    fn charge(&self) -> i32 {
+        match self.anti() {
+            Antiness::Ordinary => 2,
+            Antiness::Anti => -2,
+        }
+    }
+
+    /// Quarks are confined and only move as part of a hadron.
+    fn all_moves(&self, _board: &Board, _pos: Ix2) -> Vec<Move> {
+        Vec::new()
+    }
+}
@@ -1,4 +1,5 @@
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Antiness {
     #[default]
     Ordinary,
@@ -10,6 +11,7 @@ pub trait AntiTrait {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Colour {
     #[default]
     White,
@@ -27,18 +29,112 @@ pub enum Colour {
     AntiMagenta,
 }
 
+impl Colour {
+    /// Red, green and blue parts of the colour charge. Secondary colours are
+    /// the sum of two primaries and anticolours count negatively.
+    pub fn components(self) -> [i32; 3] {
+        match self {
+            Colour::White => [0, 0, 0],
+            Colour::Red => [1, 0, 0],
+            Colour::Green => [0, 1, 0],
+            Colour::Blue => [0, 0, 1],
+            Colour::Yellow => [1, 1, 0],
+            Colour::Cyan => [0, 1, 1],
+            Colour::Magenta => [1, 0, 1],
+            Colour::AntiRed => [-1, 0, 0],
+            Colour::AntiGreen => [0, -1, 0],
+            Colour::AntiBlue => [0, 0, -1],
+            Colour::AntiYellow => [-1, -1, 0],
+            Colour::AntiCyan => [0, -1, -1],
+            Colour::AntiMagenta => [-1, 0, -1],
+        }
+    }
+
+    /// Net colour of a group of colour charges, or `None` if it is not a
+    /// single colour. Equal amounts of red, green and blue cancel out, so an
+    /// anticolour is reported as its complementary colour.
+    pub fn mix(colours: impl IntoIterator<Item = Colour>) -> Option<Colour> {
+        let mut total = [0; 3];
+        for colour in colours {
+            for (sum, part) in total.iter_mut().zip(colour.components()) {
+                *sum += part;
+            }
+        }
+        let min = *total.iter().min().expect("three components");
+
+        Some(match total.map(|part| part - min) {
+            [0, 0, 0] => Colour::White,
+            [1, 0, 0] => Colour::Red,
+            [0, 1, 0] => Colour::Green,
+            [0, 0, 1] => Colour::Blue,
+            [1, 1, 0] => Colour::Yellow,
+            [0, 1, 1] => Colour::Cyan,
+            [1, 0, 1] => Colour::Magenta,
+            _ => return None,
+        })
+    }
+
+    pub fn is_neutral(colours: impl IntoIterator<Item = Colour>) -> bool {
+        Self::mix(colours) == Some(Colour::White)
+    }
+
+    /// Whether this is a colour, carried by quarks, or an anticolour, carried
+    /// by antiquarks. White is neither.
+    pub fn antiness(self) -> Option<Antiness> {
+        match self {
+            Colour::White => None,
+            Colour::Red
+            | Colour::Green
+            | Colour::Blue
+            | Colour::Yellow
+            | Colour::Cyan
+            | Colour::Magenta => Some(Antiness::Ordinary),
+            Colour::AntiRed
+            | Colour::AntiGreen
+            | Colour::AntiBlue
+            | Colour::AntiYellow
+            | Colour::AntiCyan
+            | Colour::AntiMagenta => Some(Antiness::Anti),
+        }
+    }
+}
+
 pub trait ColourTrait {
     fn colour(&self) -> Colour;
 }
 
-#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
-pub enum Mass {
-    Massless,
-    Light,
-    Medium,
-    Heavy,
-    Motionless,
-    Unstable,
+pub use self::mass::Mass;
+
+// The derived impls name every variant, including the deprecated one, and
+// only take their lints from the module they are in.
+#[allow(deprecated)]
+mod mass {
+    #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Mass {
+        Massless,
+        Light,
+        Medium,
+        Heavy,
+        Motionless,
+        /// Instability is not a mass: particles that decay have a lifetime
+        /// instead, see [`crate::particle::ParticleTrait::lifetime`]. No
+        /// particle has this mass and it is pushed like [`Mass::Medium`].
+        #[deprecated(note = "unstable particles have a lifetime instead of a mass")]
+        Unstable,
+    }
+
+    impl Mass {
+        /// Whether a particle of this mass shoves a particle of mass `other`
+        /// that it runs into instead of stopping against it.
+        pub fn pushes(self, other: Mass) -> bool {
+            self == Mass::Heavy
+                && matches!(
+                    other,
+                    Mass::Massless | Mass::Light | Mass::Medium | Mass::Unstable
+                )
+        }
+    }
 }
 
 pub trait MassTrait {
@@ -46,6 +142,7 @@ pub trait MassTrait {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Right,
     Down,
@@ -54,6 +151,7 @@ pub enum Direction {
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Step {
     #[default]
     Continue,
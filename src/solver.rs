@@ -1,110 +1,681 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use slotmap::Key;
 
-use crate::{board::Board, tree::Tree};
+use crate::board::{Board, PackedBoard, PackedLevel, Symmetry};
+use crate::{movement::Move, tree::Tree};
+
+#[cfg(feature = "serde")]
+pub use self::checkpoint::{CheckpointError, CHECKPOINT_VERSION};
+pub use self::dot::{DotLabel, DotOptions};
+pub use self::heuristic::{GoalDistance, Heuristic};
+pub use self::ida::IdaSolver;
+pub use self::progress::{CancellationToken, Limit, Limits, Observer, Progress, SolveStatus};
+pub use self::prune::{Pruner, TooFewParticles, UnreachableGoals};
+pub use self::solution::Solution;
+
+#[cfg(feature = "serde")]
+mod checkpoint;
+mod dot;
+mod heuristic;
+mod ida;
+mod progress;
+mod prune;
+mod solution;
+
+/// Boards each thread expands between two checks of the [`Limits`].
+const CHUNK_PER_THREAD: usize = 64;
 
 pub struct Solver<K: Key> {
-    tree: Tree<K, Board>,
+    level: PackedLevel,
+    tree: Tree<K, PackedBoard>,
+    /// Boards of the current layer a limit stopped the solve before, which
+    /// are expanded before the next layer starts.
+    layer: Vec<K>,
     next_states: Vec<K>,
-    board_to_key: HashMap<Board, K>,
+    board_to_key: HashMap<PackedBoard, K>,
+    threads: usize,
+    pruners: Vec<Box<dyn Pruner>>,
+    symmetries: Vec<Symmetry>,
+    depth: usize,
 }
 
 impl<K: Key> Solver<K> {
-    pub fn tree(&self) -> &Tree<K, Board> {
+    /// The explored boards, packed by [`Self::level`]. Use [`Self::board`] to
+    /// get the [`Board`] stored under a key.
+    pub fn tree(&self) -> &Tree<K, PackedBoard> {
         &self.tree
     }
 
+    /// The level every board in [`Self::tree`] is packed by.
+    pub fn level(&self) -> &PackedLevel {
+        &self.level
+    }
+
+    /// The board stored under `key`, unpacked.
+    pub fn board(&self, key: K) -> Board {
+        self.level
+            .unpack(self.tree.get_value(key).expect("Board not found"))
+    }
+
     pub fn next_states(&self) -> &[K] {
         &self.next_states
     }
 
-    pub fn board_to_key(&self) -> &HashMap<Board, K> {
+    /// Boards of the current layer still to be expanded, empty between
+    /// layers.
+    pub fn layer(&self) -> &[K] {
+        &self.layer
+    }
+
+    pub fn board_to_key(&self) -> &HashMap<PackedBoard, K> {
         &self.board_to_key
     }
 
+    /// Number of layers expanded so far.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Every shortest path from the root to each of the `solved` boards
+    /// returned by a solve, as the boards along it.
+    pub fn solution_paths<'a>(&'a self, solved: &'a [K]) -> impl Iterator<Item = Vec<Board>> + 'a {
+        solved.iter().flat_map(|&key| {
+            self.tree
+                .paths_to(key)
+                .expect("Board not found")
+                .map(|path| self.oriented(path.into_iter().map(|key| self.board(key)).collect()))
+        })
+    }
+
+    /// [`Self::solution_paths`] as the moves between the boards.
+    pub fn solution_moves<'a>(&'a self, solved: &'a [K]) -> impl Iterator<Item = Vec<Move>> + 'a {
+        self.solution_paths(solved).map(|boards| {
+            boards
+                .windows(2)
+                .map(|pair| {
+                    pair[0]
+                        .move_to(&pair[1])
+                        .expect("boards are one move apart")
+                })
+                .collect()
+        })
+    }
+
+    /// [`Self::solution_paths`] as [`Solution`]s.
+    pub fn solutions<'a>(&'a self, solved: &'a [K]) -> impl Iterator<Item = Solution> + 'a {
+        self.solution_paths(solved)
+            .map(|boards| Solution::from_boards(boards, self.tree.len()))
+    }
+
+    /// One shortest path from the root to `key`.
+    pub fn solution(&self, key: K) -> Solution {
+        let mut boards = Vec::new();
+        let mut current = Some(key);
+        while let Some(key) = current {
+            boards.push(self.board(key));
+            current = self.tree.parents_of(key).unwrap().first().copied();
+        }
+        boards.reverse();
+
+        Solution::from_boards(self.oriented(boards), self.tree.len())
+    }
+
+    /// Turns each board of a path from the root into the image of it that the
+    /// board before actually moves to, undoing the canonicalization of
+    /// [`Self::with_symmetries`].
+    fn oriented(&self, mut boards: Vec<Board>) -> Vec<Board> {
+        if self.symmetries.is_empty() {
+            return boards;
+        }
+        for i in 1..boards.len() {
+            let next_boards = boards[i - 1].next_boards();
+            boards[i] = boards[i]
+                .images(&self.symmetries)
+                .into_iter()
+                .find(|image| next_boards.contains(image))
+                .expect("boards are one move apart");
+        }
+
+        boards
+    }
+
+    /// Solves breadth first and returns one of the shortest solutions.
+    pub fn find_solution(&mut self) -> Option<Solution> {
+        let solved = self.solve();
+        solved.first().map(|&key| self.solution(key))
+    }
+
+    /// Number of paths [`Self::solution_paths`] yields, counted without
+    /// listing them.
+    pub fn count_solutions(&self, solved: &[K]) -> u128 {
+        solved.iter().fold(0, |sum, &key| {
+            sum.saturating_add(self.tree.count_paths_to(key).expect("Board not found"))
+        })
+    }
+
     pub fn new(root: Board) -> Self {
-        let tree = Tree::new(root.clone());
+        let level = PackedLevel::new(&root, &[]);
+        let packed = level.pack(&root);
+        let tree = Tree::new(packed.clone());
         let next_states = vec![tree.root()];
         let mut board_to_key = HashMap::new();
-        board_to_key.insert(root, tree.root());
+        board_to_key.insert(packed, tree.root());
 
         Self {
+            level,
             tree,
+            layer: Vec::new(),
             next_states,
             board_to_key,
+            threads: 1,
+            pruners: Vec::new(),
+            symmetries: Vec::new(),
+            depth: 0,
         }
     }
 
+    /// Expands each step's boards on up to `threads` threads. Successors are
+    /// still added to the tree in frontier order, so the result is the same
+    /// as with a single thread.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Stores each board as the canonical representative of its images under
+    /// those of `symmetries` the level is symmetric under, see
+    /// [`Board::canonical`]. Must be set before solving.
+    pub fn with_symmetries(mut self, symmetries: &[Symmetry]) -> Self {
+        let root = self.board(self.tree.root());
+        self.symmetries = symmetries
+            .iter()
+            .copied()
+            .filter(|&symmetry| root.is_level_symmetric(symmetry))
+            .collect();
+        self.level = PackedLevel::new(&root, &self.symmetries);
+        self.reset(&root);
+        self
+    }
+
+    /// Drops everything explored and starts again from `root`.
+    fn reset(&mut self, root: &Board) {
+        self.tree = Tree::new(self.level.pack(root));
+        self.board_to_key = HashMap::from([(
+            self.level.pack(&root.canonical(&self.symmetries)),
+            self.tree.root(),
+        )]);
+        self.layer.clear();
+        self.next_states = vec![self.tree.root()];
+        self.depth = 0;
+    }
+
+    /// Drops the boards `pruner` finds dead before they are added to the tree.
+    pub fn with_pruner(mut self, pruner: impl Pruner + 'static) -> Self {
+        self.pruners.push(Box::new(pruner));
+        self
+    }
+
     pub fn solve(&mut self) -> Vec<K> {
-        let mut solved = self.one_step();
-        while !self.next_states.is_empty() && solved.is_empty() {
-            solved = self.one_step();
+        match self.solve_with(&Limits::default(), &mut |_: &Progress| ()) {
+            SolveStatus::Solved(solved) => solved,
+            _ => Vec::new(),
         }
-
-        solved
     }
 
     pub fn verbose_solve(&mut self) -> Vec<K> {
-        let mut solved = self.one_step();
-        let mut n = 1;
-        while !self.next_states.is_empty() && solved.is_empty() {
-            println!("solving: step {n}");
-            n += 1;
-            solved = self.one_step();
+        let mut observer = |progress: &Progress| {
+            println!(
+                "solving: step {}, {} boards in frontier, {} states, {:.2?}",
+                progress.depth, progress.frontier, progress.states, progress.elapsed
+            )
+        };
+        match self.solve_with(&Limits::default(), &mut observer) {
+            SolveStatus::Solved(solved) => solved,
+            _ => Vec::new(),
         }
+    }
+
+    /// Solves breadth first like [`Self::solve`], reporting to `observer`
+    /// after every layer and stopping once any of `limits` is reached.
+    pub fn solve_with(&mut self, limits: &Limits, observer: &mut impl Observer) -> SolveStatus<K> {
+        let start = Instant::now();
+        loop {
+            if self.layer.is_empty() && self.next_states.is_empty() {
+                return SolveStatus::Unsolvable;
+            }
 
-        solved
+            let solved = match self.step(limits, start) {
+                Ok(solved) => solved,
+                Err(limit) => return SolveStatus::LimitReached(limit),
+            };
+            observer.on_layer(&self.progress(start.elapsed()));
+            if !solved.is_empty() {
+                return SolveStatus::Solved(solved);
+            }
+        }
     }
 
-    pub fn solve_n_steps(&mut self, steps: u32) -> Vec<K> {
-        if steps == 0 {
-            return Vec::new();
+    fn progress(&self, elapsed: Duration) -> Progress {
+        Progress {
+            depth: self.depth,
+            frontier: self.next_states.len(),
+            states: self.tree.len(),
+            elapsed,
         }
-        let mut solved = self.one_step();
-        for _ in 0..steps - 1 {
-            if self.next_states.is_empty() || solved.is_empty() {
+    }
+
+    /// Expands at most `steps` more layers and returns the solved boards of
+    /// the first layer that has any. The frontier is kept, so calling it again
+    /// continues the search where it stopped.
+    pub fn solve_n_steps(&mut self, steps: u32) -> Vec<K> {
+        for _ in 0..steps {
+            if self.layer.is_empty() && self.next_states.is_empty() {
                 break;
             }
-            solved = self.one_step();
+            let solved = self.one_step();
+            if !solved.is_empty() {
+                return solved;
+            }
         }
 
-        solved
+        Vec::new()
     }
 
+    /// Expands one layer, or the rest of the current one if a limit stopped
+    /// it, and returns its solved boards.
     pub fn one_step(&mut self) -> Vec<K> {
-        let mut solved_boards = Vec::new();
-        let current_states = self.next_states.clone();
-        self.next_states = Vec::new();
+        self.step(&Limits::default(), Instant::now())
+            .expect("no limits are set")
+    }
 
-        for &current_key in &current_states {
-            for next_board in self
-                .tree
-                .get_value(current_key)
-                .expect("Board not found")
+    /// [`Self::one_step`], checking `limits` before each board is expanded.
+    /// A layer that runs into one keeps its remaining boards in
+    /// [`Self::layer`], so the next step continues it.
+    fn step(&mut self, limits: &Limits, start: Instant) -> Result<Vec<K>, Limit> {
+        if self.depth == 0 && self.tree.len() > 1 {
+            // Only an A* search leaves boards in the tree before the first
+            // layer, see [`Self::solve_astar`].
+            let root = self.board(self.tree.root());
+            self.reset(&root);
+        }
+        if self.layer.is_empty() {
+            self.layer = std::mem::take(&mut self.next_states);
+            self.depth += 1;
+        }
+
+        let mut done = 0;
+        while done < self.layer.len() {
+            let end = self.layer.len().min(done + self.threads * CHUNK_PER_THREAD);
+            let chunk = self.layer[done..end].to_vec();
+            let successors = self.expand(&chunk);
+            for (current_key, next_boards) in chunk.into_iter().zip(successors) {
+                if let Some(limit) = limits.reached(&self.progress(start.elapsed())) {
+                    self.layer.drain(..done);
+                    return Err(limit);
+                }
+                self.add_successors(current_key, next_boards);
+                done += 1;
+            }
+        }
+        self.layer.clear();
+
+        Ok(self
+            .next_states
+            .iter()
+            .copied()
+            .filter(|&key| {
+                self.level
+                    .is_solved(self.tree.get_value(key).expect("Board not found"))
+            })
+            .collect())
+    }
+
+    /// Adds the boards `current_key` leads to, linking those already in the
+    /// next layer and skipping those seen in earlier ones.
+    fn add_successors(&mut self, current_key: K, next_boards: Vec<PackedBoard>) {
+        for next_board in next_boards {
+            match self.board_to_key.get(&next_board) {
+                Some(next_key) => {
+                    if self.next_states.contains(next_key) {
+                        self.tree.connect_unchecked(current_key, *next_key).unwrap();
+                    }
+                }
+                None => {
+                    let new_key = self
+                        .tree
+                        .insert_child_of(current_key, next_board.clone())
+                        .expect("Parent not found");
+                    self.next_states.push(new_key);
+                    self.board_to_key.insert(next_board, new_key);
+                }
+            };
+        }
+    }
+
+    /// Successors of each of `states` that are not pruned, in the same order.
+    fn expand(&self, states: &[K]) -> Vec<Vec<PackedBoard>> {
+        let boards: Vec<&PackedBoard> = states
+            .iter()
+            .map(|&key| self.tree.get_value(key).expect("Board not found"))
+            .collect();
+        let level = &self.level;
+        let pruners = &self.pruners;
+        let symmetries = &self.symmetries;
+        let successors = move |board: &&PackedBoard| -> Vec<PackedBoard> {
+            let mut board = level.unpack(board);
+            board
                 .all_moves()
-            {
-                match self.board_to_key.get(&next_board) {
-                    Some(next_key) => {
-                        if self.next_states.contains(next_key) {
-                            self.tree.connect(current_key, *next_key).unwrap();
-                        }
+                .iter()
+                .filter_map(|m| {
+                    board.apply(m);
+                    let next_board = if pruners.is_dead(&board) {
+                        None
+                    } else if symmetries.is_empty() {
+                        Some(level.pack(&board))
+                    } else {
+                        Some(level.pack(&board.canonical(symmetries)))
+                    };
+                    board.undo(m);
+                    next_board
+                })
+                .collect()
+        };
+        if self.threads == 1 || boards.len() < 2 {
+            return boards.iter().map(successors).collect();
+        }
+
+        let chunk_size = boards.len().div_ceil(self.threads);
+        thread::scope(|scope| {
+            let handles: Vec<_> = boards
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(successors).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("expansion thread panicked"))
+                .collect()
+        })
+    }
+
+    /// Searches from the root again with A*, replacing whatever the solver has
+    /// explored so far. Boards are only added to the tree once their shortest
+    /// distance is known, so with a [`Heuristic`] that never overestimates and
+    /// never drops by more than one per move, the returned solved boards are
+    /// reached by optimal paths through `parents_of`, just like [`Self::solve`].
+    ///
+    /// The tree then holds only part of each layer, so the breadth first
+    /// frontier is left at the root with a depth of zero: a later
+    /// [`Self::solve`] or [`Self::one_step`] drops the A* boards and starts
+    /// over, which invalidates the returned keys.
+    pub fn solve_astar(&mut self, heuristic: &impl Heuristic) -> Vec<K> {
+        let root = self.board(self.tree.root());
+        self.reset(&root);
+
+        let root = self.tree.root();
+        if self
+            .level
+            .is_solved(self.tree.get_value(root).expect("Root not found"))
+        {
+            return vec![root];
+        }
+
+        let mut frontier: HashMap<PackedBoard, (u32, Vec<K>)> = HashMap::new();
+        let mut queued: HashMap<usize, PackedBoard> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        let mut next_id = 0;
+        let mut solved_boards = Vec::new();
+        let mut solved_cost = None;
+
+        let mut current = Some((root, 0));
+        loop {
+            if let Some((current_key, cost)) = current.take() {
+                for next_board in self.board(current_key).next_boards() {
+                    let next_board = next_board.canonical(&self.symmetries);
+                    if self.pruners.is_dead(&next_board) {
+                        continue;
                     }
-                    None => {
-                        let new_key = self
-                            .tree
-                            .insert_child_of(current_key, next_board.clone())
-                            .expect("Parent not found");
-                        if next_board.is_solved() {
-                            solved_boards.push(new_key);
+                    let Some(estimate) = heuristic.estimate(&next_board) else {
+                        continue;
+                    };
+                    let next_board = self.level.pack(&next_board);
+                    let next_cost = cost + 1;
+                    if let Some(&known) = self.board_to_key.get(&next_board) {
+                        // Popped before this parent, at the same cost.
+                        let depth = self.tree.depth_of(known).expect("Board not found");
+                        if depth == next_cost as usize {
+                            self.tree.connect_unchecked(current_key, known).unwrap();
                         }
-                        self.next_states.push(new_key);
-                        self.board_to_key.insert(next_board, new_key);
+                        continue;
+                    }
+                    let entry = frontier
+                        .entry(next_board.clone())
+                        .or_insert((u32::MAX, Vec::new()));
+                    if next_cost == entry.0 {
+                        entry.1.push(current_key);
+                        continue;
                     }
-                };
+                    if next_cost > entry.0 {
+                        continue;
+                    }
+                    *entry = (next_cost, vec![current_key]);
+
+                    queued.insert(next_id, next_board);
+                    queue.push((Reverse(next_cost + estimate), next_cost, Reverse(next_id)));
+                    next_id += 1;
+                }
+            }
+
+            let Some((Reverse(total), cost, Reverse(id))) = queue.pop() else {
+                break;
+            };
+            if solved_cost.is_some_and(|solved_cost| total > solved_cost) {
+                break;
+            }
+            let board = queued.remove(&id).expect("Board not queued");
+            match frontier.get(&board) {
+                Some((best_cost, _)) if *best_cost == cost => (),
+                _ => continue,
+            }
+            let (_, parents) = frontier.remove(&board).expect("Board not queued");
+
+            let key = self
+                .tree
+                .insert_child_of(parents[0], board.clone())
+                .expect("Parent not found");
+            for &parent in &parents[1..] {
+                self.tree.connect_unchecked(parent, key).unwrap();
+            }
+            let is_solved = self.level.is_solved(&board);
+            self.board_to_key.insert(board, key);
+            match is_solved {
+                true => {
+                    solved_cost = Some(cost);
+                    solved_boards.push(key);
+                }
+                false => current = Some((key, cost)),
             }
         }
 
         solved_boards
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+    use slotmap::DefaultKey;
+
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Tau};
+
+    use super::*;
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut board = Board::new(6, 6, vec![Ix2(4, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(3, 1));
+        board.add_particle(Tau::default(), Ix2(2, 2));
+        board.add_particle(Neutron::default(), Ix2(2, 0));
+        board.add_obstacle(Block, Ix2(5, 5));
+        board.add_obstacle(Hole, Ix2(4, 3));
+        board.add_obstacle(Hole, Ix2(1, 5));
+
+        let mut sequential: Solver<DefaultKey> = Solver::new(board.clone());
+        let expected = sequential.solve();
+        assert!(!expected.is_empty());
+
+        for threads in [2, 3, 8] {
+            let mut parallel: Solver<DefaultKey> = Solver::new(board.clone()).with_threads(threads);
+            assert_eq!(parallel.solve(), expected);
+            assert_eq!(parallel.tree().len(), sequential.tree().len());
+            assert_eq!(parallel.board_to_key(), sequential.board_to_key());
+            for &key in &expected {
+                assert_eq!(
+                    parallel.tree().parents_of(key).unwrap(),
+                    sequential.tree().parents_of(key).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn solve_with_limits() {
+        let mut board = Board::new(6, 6, vec![Ix2(4, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(3, 1));
+        board.add_particle(Tau::default(), Ix2(2, 2));
+        board.add_particle(Neutron::default(), Ix2(2, 0));
+        board.add_obstacle(Hole, Ix2(4, 3));
+
+        let mut layers = Vec::new();
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        let limits = Limits {
+            max_states: Some(40),
+            ..Limits::default()
+        };
+        let status = solver.solve_with(&limits, &mut |progress: &Progress| layers.push(*progress));
+        assert_eq!(status, SolveStatus::LimitReached(Limit::States));
+        assert!(layers
+            .windows(2)
+            .all(|pair| pair[0].depth + 1 == pair[1].depth));
+        let last = layers.last().unwrap();
+        assert_eq!(last.states, 27);
+        // Stopped partway through the layer that ends with 61 boards.
+        assert_eq!(solver.depth(), last.depth + 1);
+        assert_eq!(solver.tree().len(), 40);
+        assert!(!solver.layer().is_empty());
+
+        let status = solver.solve_with(&Limits::default(), &mut |_: &Progress| ());
+        let SolveStatus::Solved(solved) = status else {
+            panic!("expected a solution, got {status:?}");
+        };
+        assert_eq!(solved, Solver::new(board.clone()).solve());
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let limits = Limits {
+            cancellation: Some(cancellation),
+            ..Limits::default()
+        };
+        let mut solver: Solver<DefaultKey> = Solver::new(board);
+        let status = solver.solve_with(&limits, &mut |_: &Progress| ());
+        assert_eq!(status, SolveStatus::LimitReached(Limit::Cancelled));
+        assert_eq!(solver.tree().len(), 1);
+
+        let mut solver: Solver<DefaultKey> = Solver::new(Board::new(1, 1, vec![].into()));
+        let status = solver.solve_with(&Limits::default(), &mut |_: &Progress| ());
+        assert_eq!(status, SolveStatus::Unsolvable);
+    }
+
+    #[test]
+    fn solve_n_steps_resumes() {
+        let mut board = Board::new(5, 5, vec![Ix2(3, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(1, 1));
+        board.add_particle(Tau::default(), Ix2(2, 4));
+        board.add_particle(Neutron::default(), Ix2(0, 3));
+        board.add_obstacle(Block, Ix2(4, 2));
+        board.add_obstacle(Hole, Ix2(3, 0));
+        board.add_obstacle(Hole, Ix2(1, 4));
+
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        assert!(solver.solve_n_steps(0).is_empty());
+        assert!(solver.solve_n_steps(2).is_empty());
+        assert_eq!(solver.depth(), 2);
+        assert!(!solver.next_states().is_empty());
+
+        let solved = solver.solve_n_steps(5);
+        assert_eq!(solver.depth(), 3);
+        assert_eq!(solved, Solver::new(board).solve());
+    }
+
+    #[test]
+    fn symmetric_deduplication() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 0));
+        board.add_obstacle(Block, Ix2(3, 2));
+        board.add_obstacle(Block, Ix2(2, 3));
+        board.add_obstacle(Block, Ix2(3, 0));
+        board.add_obstacle(Block, Ix2(0, 3));
+
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        let expected = solver.find_solution().unwrap();
+
+        let mut symmetric: Solver<DefaultKey> =
+            Solver::new(board.clone()).with_symmetries(&Symmetry::ALL);
+        let solution = symmetric.find_solution().unwrap();
+        assert_eq!(solution.depth(), expected.depth());
+        assert!(solution.explored() < expected.explored());
+        assert_eq!(solution.boards()[0], board);
+        let mut replayed = board.clone();
+        for m in solution.moves() {
+            replayed.apply(m);
+        }
+        assert!(replayed.is_solved());
+
+        let solved = symmetric.solve_astar(&GoalDistance);
+        assert_eq!(symmetric.solution(solved[0]).depth(), expected.depth());
+    }
+
+    #[test]
+    fn all_shortest_solutions() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 0));
+        board.add_obstacle(Block, Ix2(3, 2));
+        board.add_obstacle(Block, Ix2(2, 3));
+        board.add_obstacle(Block, Ix2(3, 0));
+        board.add_obstacle(Block, Ix2(0, 3));
+
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        let solved = solver.solve();
+        let paths: Vec<_> = solver.solution_paths(&solved).collect();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(solver.count_solutions(&solved), 2);
+        for path in &paths {
+            assert_eq!(path.len(), 3);
+            assert_eq!(path[0], board);
+            assert!(path[2].is_solved());
+        }
+
+        assert_eq!(solver.solutions(&solved).count(), 2);
+        let mut astar: Solver<DefaultKey> = Solver::new(board.clone());
+        let astar_solved = astar.solve_astar(&GoalDistance);
+        assert_eq!(astar.count_solutions(&astar_solved), 2);
+        let solution = Solver::<DefaultKey>::new(board.clone())
+            .find_solution()
+            .unwrap();
+        assert_eq!(solution.depth(), 2);
+        assert_eq!(solution.boards(), &paths[0][..]);
+        assert_eq!(solution.explored(), solver.tree().len());
+
+        for moves in solver.solution_moves(&solved) {
+            let mut replayed = board.clone();
+            for m in &moves {
+                replayed.apply(m);
+            }
+            assert!(replayed.is_solved());
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Saving a [`Solver`]'s search to a file and resuming it later.
+//!
+//! Slot map keys mean nothing outside the tree they came from, so nodes are
+//! written as a list with parents before children and refer to each other by
+//! index. Loading rebuilds the tree in that order, which gives every node a
+//! new key, and the map from boards to keys along with it.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use slotmap::Key;
+
+use crate::board::{Board, PackedBoard, Symmetry};
+use crate::tree::Tree;
+
+use super::Solver;
+
+/// Version written by [`Solver::save_checkpoint`].
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    UnsupportedVersion(u32),
+    /// The checkpoint was saved by a solver with a different root board.
+    DifferentRoot,
+    /// The checkpoint was saved by a solver deduplicating other symmetries.
+    DifferentSymmetries,
+    /// A node refers to a missing node or a board does not fit the level.
+    Malformed,
+}
+
+impl Error for CheckpointError {}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "checkpoint file error: {error}"),
+            Self::Format(error) => write!(f, "checkpoint is not valid: {error}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported checkpoint version {version}, expected {CHECKPOINT_VERSION}"
+            ),
+            Self::DifferentRoot => write!(f, "checkpoint belongs to a different root board"),
+            Self::DifferentSymmetries => {
+                write!(f, "checkpoint was saved with different symmetries")
+            }
+            Self::Malformed => write!(f, "checkpoint is malformed"),
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Format(error)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    version: u32,
+    root: Board,
+    symmetries: Vec<Symmetry>,
+    depth: usize,
+    /// Each board with the indices of its parents, root first and every
+    /// parent before its children.
+    nodes: Vec<(PackedBoard, Vec<usize>)>,
+    /// The rest of a layer a limit cut short, see [`Solver::layer`].
+    layer: Vec<usize>,
+    next_states: Vec<usize>,
+}
+
+impl<K: Key> Solver<K> {
+    /// Writes the tree and the frontier to `path`, so the search can be
+    /// continued with [`Solver::load_checkpoint`]. A checkpoint already at
+    /// `path` is only replaced once the new one is completely written.
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let order = self.tree.topological_order();
+        let index: HashMap<K, usize> = order.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+
+        let nodes = order
+            .iter()
+            .map(|&key| {
+                let parents = self.tree.parents_of(key).expect("Board not found");
+                (
+                    self.tree.get_value(key).expect("Board not found").clone(),
+                    parents.iter().map(|parent| index[parent]).collect(),
+                )
+            })
+            .collect();
+        let checkpoint = Checkpoint {
+            version: CHECKPOINT_VERSION,
+            root: self.board(self.tree.root()),
+            symmetries: self.symmetries.clone(),
+            depth: self.depth,
+            nodes,
+            layer: self.layer.iter().map(|key| index[key]).collect(),
+            next_states: self.next_states.iter().map(|key| index[key]).collect(),
+        };
+
+        // Written next to `path` and moved over it once complete, so a failed
+        // save leaves the previous checkpoint intact.
+        let path = path.as_ref();
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        let written = write_checkpoint(&temp, &checkpoint);
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+            return written;
+        }
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Replaces the search so far with the one saved at `path`. The solver
+    /// must have been created from the same root with the same symmetries;
+    /// threads and pruners are kept as configured. Keys from before the save
+    /// are not valid afterwards.
+    pub fn load_checkpoint(&mut self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let checkpoint: Checkpoint = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(checkpoint.version));
+        }
+        if checkpoint.root != self.board(self.tree.root()) {
+            return Err(CheckpointError::DifferentRoot);
+        }
+        if checkpoint.symmetries != self.symmetries {
+            return Err(CheckpointError::DifferentSymmetries);
+        }
+
+        let repack =
+            |board: &PackedBoard| self.level.repack(board).ok_or(CheckpointError::Malformed);
+        let mut nodes = checkpoint.nodes.iter();
+        let Some((root, _)) = nodes.next() else {
+            return Err(CheckpointError::Malformed);
+        };
+        let mut tree = Tree::new(repack(root)?);
+        let mut keys = vec![tree.root()];
+        // The root is stored as given and known by its canonical image, while
+        // every other board is stored canonical.
+        let canonical_root = self
+            .level
+            .pack(&checkpoint.root.canonical(&self.symmetries));
+        let mut board_to_key = HashMap::from([(canonical_root, tree.root())]);
+        for (board, parents) in nodes {
+            let mut parents = parents
+                .iter()
+                .map(|&parent| keys.get(parent).copied().ok_or(CheckpointError::Malformed));
+            let first = parents.next().ok_or(CheckpointError::Malformed)??;
+            let board = repack(board)?;
+            let key = tree
+                .insert_child_of(first, board.clone())
+                .expect("Parent not found");
+            for parent in parents {
+                tree.connect_unchecked(parent?, key)
+                    .expect("Board not found");
+            }
+            board_to_key.insert(board, key);
+            keys.push(key);
+        }
+
+        let key = |i: usize| keys.get(i).copied().ok_or(CheckpointError::Malformed);
+        let layer = checkpoint
+            .layer
+            .iter()
+            .map(|&i| key(i))
+            .collect::<Result<_, _>>()?;
+        let next_states = checkpoint
+            .next_states
+            .iter()
+            .map(|&i| key(i))
+            .collect::<Result<_, _>>()?;
+
+        self.tree = tree;
+        self.layer = layer;
+        self.next_states = next_states;
+        self.board_to_key = board_to_key;
+        self.depth = checkpoint.depth;
+        Ok(())
+    }
+}
+
+fn write_checkpoint(path: &Path, checkpoint: &Checkpoint) -> Result<(), CheckpointError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, checkpoint)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+    use slotmap::DefaultKey;
+
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Tau};
+    use crate::solver::{Limits, Progress};
+
+    use super::*;
+
+    /// Removes the file at its path when the test ends, even on a failed
+    /// assertion.
+    struct TempFile(std::path::PathBuf);
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn checkpoint_resumes() {
+        let mut board = Board::new(5, 5, vec![Ix2(3, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(1, 1));
+        board.add_particle(Tau::default(), Ix2(2, 4));
+        board.add_particle(Neutron::default(), Ix2(0, 3));
+        board.add_obstacle(Block, Ix2(4, 2));
+        board.add_obstacle(Hole, Ix2(3, 0));
+        board.add_obstacle(Hole, Ix2(1, 4));
+        let file = TempFile(
+            std::env::temp_dir().join(format!("higgs-checkpoint-{}.json", std::process::id())),
+        );
+        let path = &file.0;
+
+        let mut expected: Solver<DefaultKey> = Solver::new(board.clone());
+        let expected = expected.find_solution().unwrap();
+
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        solver.solve_n_steps(2);
+        solver.save_checkpoint(path).unwrap();
+
+        let mut resumed: Solver<DefaultKey> = Solver::new(board.clone()).with_threads(2);
+        resumed.load_checkpoint(path).unwrap();
+        assert_eq!(resumed.depth(), 2);
+        assert_eq!(resumed.tree().len(), solver.tree().len());
+        assert_eq!(resumed.next_states().len(), solver.next_states().len());
+        assert_eq!(resumed.board_to_key().len(), solver.board_to_key().len());
+        assert!(solver
+            .board_to_key()
+            .keys()
+            .all(|board| resumed.board_to_key().contains_key(board)));
+        let solution = resumed.find_solution().unwrap();
+        assert_eq!(solution.depth(), expected.depth());
+        assert_eq!(solution.explored(), expected.explored());
+
+        let mut limited: Solver<DefaultKey> = Solver::new(board.clone());
+        let limits = Limits {
+            max_states: Some(solver.tree().len() + 5),
+            ..Limits::default()
+        };
+        limited.solve_with(&limits, &mut |_: &Progress| ());
+        assert!(!limited.layer().is_empty());
+        limited.save_checkpoint(path).unwrap();
+        let mut resumed: Solver<DefaultKey> = Solver::new(board.clone());
+        resumed.load_checkpoint(path).unwrap();
+        assert_eq!(resumed.layer().len(), limited.layer().len());
+        let solution = resumed.find_solution().unwrap();
+        assert_eq!(solution.depth(), expected.depth());
+        assert_eq!(solution.explored(), expected.explored());
+
+        // A save that fails leaves the last checkpoint in place.
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::create_dir(&temp).unwrap();
+        let saved = solver.save_checkpoint(path);
+        std::fs::remove_dir(&temp).unwrap();
+        assert!(matches!(saved, Err(CheckpointError::Io(_))));
+        resumed.load_checkpoint(path).unwrap();
+        assert_eq!(resumed.layer().len(), limited.layer().len());
+
+        let mut other = board.clone();
+        other.remove_obstacle(Ix2(4, 2));
+        let mut different: Solver<DefaultKey> = Solver::new(other);
+        assert!(matches!(
+            different.load_checkpoint(path),
+            Err(CheckpointError::DifferentRoot)
+        ));
+        assert_eq!(different.tree().len(), 1);
+    }
+}
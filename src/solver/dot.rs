@@ -0,0 +1,170 @@
+//! Graphviz export of the search graph.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use slotmap::Key;
+
+use crate::movement::Move;
+
+use super::Solver;
+
+/// What each node of an exported graph shows.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub enum DotLabel {
+    /// The board in the plain-text level format.
+    #[default]
+    Board,
+    /// The move from the node's first parent.
+    Move,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct DotOptions {
+    /// Deepest layer to include, counting the root as layer 0.
+    pub max_depth: Option<usize>,
+    pub label: DotLabel,
+}
+
+impl<K: Key> Solver<K> {
+    /// Writes the tree as a Graphviz digraph with an edge for every entry of
+    /// `parents`. The `solved` boards and every node and edge on a shortest
+    /// path to them are highlighted.
+    pub fn write_dot(
+        &self,
+        out: &mut impl Write,
+        solved: &[K],
+        options: &DotOptions,
+    ) -> io::Result<()> {
+        let included: Vec<K> = self
+            .tree
+            .topological_order()
+            .into_iter()
+            .filter(|&key| {
+                options
+                    .max_depth
+                    .is_none_or(|max| self.tree.depth_of(key).unwrap() <= max)
+            })
+            .collect();
+        let ids: HashMap<K, usize> = included
+            .iter()
+            .enumerate()
+            .map(|(i, &key)| (key, i))
+            .collect();
+
+        let mut on_path: HashSet<K> = HashSet::new();
+        let mut stack = solved.to_vec();
+        while let Some(key) = stack.pop() {
+            if on_path.insert(key) {
+                stack.extend(self.tree.parents_of(key).expect("Board not found"));
+            }
+        }
+
+        writeln!(out, "digraph search {{")?;
+        writeln!(out, "    node [shape=box, fontname=\"monospace\"];")?;
+        for &key in &included {
+            let label = match options.label {
+                DotLabel::Board => self.board(key).to_string(),
+                DotLabel::Move => self
+                    .move_from_parent(key)
+                    .map_or_else(|| String::from("root"), |m| m.to_string()),
+            };
+            let style = if solved.contains(&key) {
+                ", style=filled, fillcolor=gold"
+            } else if on_path.contains(&key) {
+                ", style=filled, fillcolor=lightblue"
+            } else {
+                ""
+            };
+            writeln!(
+                out,
+                "    n{} [label=\"{}\"{style}];",
+                ids[&key],
+                escape(&label)
+            )?;
+        }
+        for &key in &included {
+            for parent in self.tree.parents_of(key).unwrap() {
+                let Some(parent_id) = ids.get(parent) else {
+                    continue;
+                };
+                let style = match on_path.contains(parent) && on_path.contains(&key) {
+                    true => " [color=red, penwidth=2]",
+                    false => "",
+                };
+                writeln!(out, "    n{parent_id} -> n{}{style};", ids[&key])?;
+            }
+        }
+        writeln!(out, "}}")
+    }
+
+    /// [`Self::write_dot`] into a string.
+    pub fn to_dot(&self, solved: &[K], options: &DotOptions) -> String {
+        let mut out = Vec::new();
+        self.write_dot(&mut out, solved, options)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("labels are valid UTF-8")
+    }
+
+    /// The move from the first parent of `key` to it, or `None` for the root.
+    fn move_from_parent(&self, key: K) -> Option<Move> {
+        let parent = self.board(*self.tree.parents_of(key).ok()?.first()?);
+        self.board(key)
+            .images(&self.symmetries)
+            .iter()
+            .find_map(|image| parent.move_to(image))
+    }
+}
+
+/// `label` as the inside of a quoted DOT string, with every line
+/// left-aligned.
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\l")
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+    use slotmap::DefaultKey;
+
+    use crate::board::Board;
+    use crate::obstacle::block::Block;
+    use crate::particle::Neutron;
+
+    use super::*;
+
+    #[test]
+    fn dot_export() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 0));
+        for pos in [Ix2(3, 2), Ix2(2, 3), Ix2(3, 0), Ix2(0, 3)] {
+            board.add_obstacle(Block, pos);
+        }
+        let mut solver: Solver<DefaultKey> = Solver::new(board);
+        let solved = solver.solve();
+
+        let dot = solver.to_dot(&solved, &DotOptions::default());
+        assert!(dot.starts_with("digraph search {\n"));
+        let edges: usize = solver
+            .tree()
+            .keys()
+            .map(|key| solver.tree().parents_of(key).unwrap().len())
+            .sum();
+        assert_eq!(dot.matches(" -> ").count(), edges);
+        assert_eq!(dot.matches("fillcolor=gold").count(), solved.len());
+        assert_eq!(dot.matches("fillcolor=lightblue").count(), 3);
+        assert!(dot.contains("n0 [label=\"n . . # .\\l"));
+
+        let options = DotOptions {
+            max_depth: Some(1),
+            label: DotLabel::Move,
+        };
+        let dot = solver.to_dot(&solved, &options);
+        assert_eq!(dot.matches("[label=").count(), 3);
+        assert!(dot.contains("n0 [label=\"root\""));
+        assert!(dot.contains("(0, 0) right: slid to (2, 0)"));
+    }
+}
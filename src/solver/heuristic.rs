@@ -0,0 +1,107 @@
+use crate::board::Board;
+use crate::particle::Particle;
+
+/// Lower bound on the number of moves a board still needs to be solved.
+pub trait Heuristic {
+    /// Never more than the number of moves left, and never dropping by more
+    /// than one per move, or `None` if the board can no longer be solved.
+    fn estimate(&self, board: &Board) -> Option<u32>;
+}
+
+impl<F: Fn(&Board) -> Option<u32>> Heuristic for F {
+    fn estimate(&self, board: &Board) -> Option<u32> {
+        self(board)
+    }
+}
+
+/// Counts the slides a particle needs to reach each unfilled goal, ignoring
+/// the electric field and obstacles. Every move carries particles along one
+/// line, so a goal needs at least one move if a particle shares its row or
+/// column and two otherwise; the estimate is the largest over all goals.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct GoalDistance;
+
+impl Heuristic for GoalDistance {
+    fn estimate(&self, board: &Board) -> Option<u32> {
+        let particles: Vec<_> = board
+            .particles()
+            .indexed_iter()
+            .filter(|(_, particle)| !matches!(particle, Particle::Empty(_)))
+            .map(|(pos, _)| pos)
+            .collect();
+
+        let mut estimate = 0;
+        for goal in board.goals().iter() {
+            let distance = particles
+                .iter()
+                .map(|&(x, y)| match (x == goal[0], y == goal[1]) {
+                    (true, true) => 0,
+                    (true, false) | (false, true) => 1,
+                    (false, false) => 2,
+                })
+                .min()?;
+            estimate = estimate.max(distance);
+        }
+
+        Some(estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+    use slotmap::DefaultKey;
+
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Tau};
+    use crate::solver::Solver;
+
+    use super::*;
+
+    #[test]
+    fn goal_distance_estimate() {
+        let mut board = Board::new(4, 4, vec![Ix2(1, 1).into(), Ix2(3, 3).into()].into());
+        assert_eq!(GoalDistance.estimate(&board), None);
+
+        board.add_particle(Electron::default(), Ix2(1, 3));
+        assert_eq!(GoalDistance.estimate(&board), Some(1));
+
+        board.add_particle(Electron::default(), Ix2(0, 0));
+        board.add_particle(Electron::default(), Ix2(1, 1));
+        assert_eq!(GoalDistance.estimate(&board), Some(1));
+    }
+
+    #[test]
+    fn astar_matches_breadth_first() {
+        let mut board = Board::new(5, 5, vec![Ix2(3, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(1, 1));
+        board.add_particle(Tau::default(), Ix2(2, 4));
+        board.add_particle(Neutron::default(), Ix2(0, 3));
+        board.add_obstacle(Block, Ix2(4, 2));
+        board.add_obstacle(Hole, Ix2(3, 0));
+        board.add_obstacle(Hole, Ix2(1, 4));
+
+        let mut breadth_first: Solver<DefaultKey> = Solver::new(board.clone());
+        let expected = breadth_first.solve();
+        assert!(!expected.is_empty());
+        let solutions = breadth_first.count_solutions(&expected);
+        let expected = breadth_first.tree().depth_of(expected[0]).unwrap();
+
+        let mut astar: Solver<DefaultKey> = Solver::new(board);
+        let solved = astar.solve_astar(&GoalDistance);
+        assert!(!solved.is_empty());
+        assert_eq!(astar.count_solutions(&solved), solutions);
+        for key in solved {
+            assert!(astar.board(key).is_solved());
+            assert_eq!(astar.tree().depth_of(key).unwrap(), expected);
+        }
+        assert!(astar.tree().len() < breadth_first.tree().len());
+        assert_eq!(astar.depth(), 0);
+        assert_eq!(astar.next_states(), &[astar.tree().root()]);
+
+        let resumed = astar.solve();
+        assert_eq!(astar.tree().depth_of(resumed[0]).unwrap(), expected);
+        assert_eq!(astar.tree().len(), breadth_first.tree().len());
+        assert_eq!(astar.depth(), breadth_first.depth());
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::board::Board;
+use crate::movement::Move;
+
+use super::{Heuristic, Solution};
+
+/// Iterative deepening A*. Only the board being searched and the moves that
+/// led to it are kept, plus a transposition table of at most
+/// `table_capacity` boards that is cleared on every iteration.
+pub struct IdaSolver {
+    board: Board,
+    path: Vec<Move>,
+    table: HashMap<Board, u32>,
+    table_capacity: usize,
+    explored: usize,
+}
+
+impl IdaSolver {
+    pub fn new(root: Board, table_capacity: usize) -> Self {
+        Self {
+            board: root,
+            path: Vec::new(),
+            table: HashMap::new(),
+            table_capacity,
+            explored: 0,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Finds a shortest sequence of moves that solves the board, or `None` if
+    /// there is none. `heuristic` must never overestimate the moves left. The
+    /// board is back at the root afterwards.
+    pub fn solve(&mut self, heuristic: &impl Heuristic) -> Option<Solution> {
+        self.explored = 0;
+        let mut bound = heuristic.estimate(&self.board)?;
+        loop {
+            self.table.clear();
+            match self.search(0, bound, heuristic) {
+                Ok(()) => {
+                    for m in self.path.iter().rev() {
+                        self.board.undo(m);
+                    }
+                    let moves = std::mem::take(&mut self.path);
+                    return Some(Solution::from_moves(
+                        self.board.clone(),
+                        moves,
+                        self.explored,
+                    ));
+                }
+                Err(u32::MAX) => return None,
+                Err(next_bound) => bound = next_bound,
+            }
+        }
+    }
+
+    /// Searches below the current board, failing with the smallest estimate
+    /// that went over `bound`.
+    fn search(&mut self, cost: u32, bound: u32, heuristic: &impl Heuristic) -> Result<(), u32> {
+        self.explored += 1;
+        let Some(estimate) = heuristic.estimate(&self.board) else {
+            return Err(u32::MAX);
+        };
+        if cost + estimate > bound {
+            return Err(cost + estimate);
+        }
+        if self.board.is_solved() {
+            return Ok(());
+        }
+
+        let table_full = self.table.len() >= self.table_capacity;
+        match self.table.get_mut(&self.board) {
+            Some(seen) if *seen <= cost => return Err(u32::MAX),
+            Some(seen) => *seen = cost,
+            None if !table_full => {
+                self.table.insert(self.board.clone(), cost);
+            }
+            None => (),
+        };
+
+        let mut next_bound = u32::MAX;
+        for m in self.board.all_moves() {
+            self.board.apply(&m);
+            self.path.push(m);
+            match self.search(cost + 1, bound, heuristic) {
+                Ok(()) => return Ok(()),
+                Err(over) => next_bound = next_bound.min(over),
+            }
+            let m = self.path.pop().expect("move was just pushed");
+            self.board.undo(&m);
+        }
+
+        Err(next_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Ix2;
+
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Tau};
+    use crate::solver::GoalDistance;
+
+    use super::*;
+
+    #[test]
+    fn ida_finds_shortest_solution() {
+        let mut board = Board::new(5, 5, vec![Ix2(3, 2).into()].into());
+        board.add_particle(Electron::default(), Ix2(1, 1));
+        board.add_particle(Tau::default(), Ix2(2, 4));
+        board.add_particle(Neutron::default(), Ix2(0, 3));
+        board.add_obstacle(Block, Ix2(4, 2));
+        board.add_obstacle(Hole, Ix2(3, 0));
+        board.add_obstacle(Hole, Ix2(1, 4));
+
+        for table_capacity in [0, 16, 1024] {
+            let mut solver = IdaSolver::new(board.clone(), table_capacity);
+            let solution = solver.solve(&GoalDistance).unwrap();
+            assert_eq!(solution.depth(), 3);
+            assert_eq!(solver.board(), &board);
+            assert_eq!(solution.boards()[0], board);
+            assert!(solution.boards()[3].is_solved());
+
+            let mut solved = board.clone();
+            for m in solution.moves() {
+                solved.apply(m);
+            }
+            assert!(solved.is_solved());
+        }
+
+        let unsolvable = Board::new(2, 2, vec![Ix2(0, 0).into()].into());
+        assert_eq!(IdaSolver::new(unsolvable, 0).solve(&GoalDistance), None);
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Where a search stands after expanding a layer.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct Progress {
+    /// Number of layers expanded so far.
+    pub depth: usize,
+    /// Boards waiting to be expanded in the next layer.
+    pub frontier: usize,
+    /// Boards stored in the tree.
+    pub states: usize,
+    pub elapsed: Duration,
+}
+
+/// Receives a [`Progress`] report after every layer of a solve.
+pub trait Observer {
+    fn on_layer(&mut self, progress: &Progress);
+}
+
+impl<F: FnMut(&Progress)> Observer for F {
+    fn on_layer(&mut self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Shared flag for stopping a solve from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// When to give up on a solve. Limits are checked before each board is
+/// expanded, and a layer they cut short is finished by the next solve.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    pub cancellation: Option<CancellationToken>,
+    /// Boards in the tree at which expanding stops. The board expanded last
+    /// may take it past this by its own successors.
+    pub max_states: Option<usize>,
+    pub max_time: Option<Duration>,
+}
+
+impl Limits {
+    /// The first limit `progress` has run into, if any.
+    pub fn reached(&self, progress: &Progress) -> Option<Limit> {
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Some(Limit::Cancelled);
+        }
+        if self.max_states.is_some_and(|max| progress.states >= max) {
+            return Some(Limit::States);
+        }
+        if self.max_time.is_some_and(|max| progress.elapsed >= max) {
+            return Some(Limit::Time);
+        }
+        None
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Limit {
+    Cancelled,
+    States,
+    Time,
+}
+
+/// How a solve ended.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub enum SolveStatus<K> {
+    /// The solved boards of the shallowest layer that has any.
+    Solved(Vec<K>),
+    /// Every reachable board was expanded without finding a solution.
+    Unsolvable,
+    /// The search stopped early; it can be continued on the same solver.
+    LimitReached(Limit),
+}
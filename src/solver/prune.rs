@@ -0,0 +1,208 @@
+use ndarray::Ix2;
+
+use crate::board::Board;
+use crate::obstacle::Obstacle;
+use crate::particle::{Particle, ParticleTrait};
+use crate::property::Direction;
+
+/// Detects boards that can never be solved, so the search drops them before
+/// they are added to the tree.
+pub trait Pruner: Send + Sync {
+    fn is_dead(&self, board: &Board) -> bool;
+}
+
+impl Pruner for Vec<Box<dyn Pruner>> {
+    fn is_dead(&self, board: &Board) -> bool {
+        self.iter().any(|pruner| pruner.is_dead(board))
+    }
+}
+
+fn particles(board: &Board) -> Vec<&Particle> {
+    board
+        .particles()
+        .iter()
+        .filter(|particle| !matches!(particle, Particle::Empty(_)))
+        .collect()
+}
+
+/// Particles are never created, so a board with fewer particles than goals
+/// stays short of them.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct TooFewParticles;
+
+impl Pruner for TooFewParticles {
+    fn is_dead(&self, board: &Board) -> bool {
+        particles(board).len() < board.goals().len()
+    }
+}
+
+/// Every particle on the board and, for those with a lifetime, everything
+/// they decay into later.
+fn forms(board: &Board) -> Vec<Particle> {
+    let mut forms: Vec<Particle> = particles(board).into_iter().copied().collect();
+    for &pos in board.lifetimes().keys() {
+        let mut particle = board.particles()[*pos];
+        while let Some(product) = particle.decay() {
+            forms.push(product);
+            if product.lifetime() == 0 {
+                break;
+            }
+            particle = product;
+        }
+    }
+    forms
+}
+
+/// A particle fills a goal by sliding along its row or column and stopping on
+/// it, so it has to come through a neighbour without a block or hole and must
+/// not run on into a hole. A container member stops wherever another member
+/// is stopped, so with containers on the board only the way in counts.
+/// Obstacles only count while nothing on the board can annihilate them away,
+/// now or once it has decayed.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct UnreachableGoals;
+
+impl UnreachableGoals {
+    fn can_stop_on(board: &Board, goal: Ix2) -> bool {
+        let obstacle = |pos: Ix2| board.obstacles()[pos];
+        if !matches!(obstacle(goal), Obstacle::Empty(_)) {
+            return false;
+        }
+        let bound = !board.containers().is_empty();
+
+        [
+            (Direction::Left, Direction::Right),
+            (Direction::Right, Direction::Left),
+            (Direction::Up, Direction::Down),
+            (Direction::Down, Direction::Up),
+        ]
+        .into_iter()
+        .any(|(from, towards)| {
+            let entered = board
+                .move_direction(from, goal)
+                .is_some_and(|pos| matches!(obstacle(pos), Obstacle::Empty(_)));
+            let stopped = bound
+                || board
+                    .move_direction(towards, goal)
+                    .is_none_or(|pos| !matches!(obstacle(pos), Obstacle::Hole(_)));
+            entered && stopped
+        })
+    }
+}
+
+impl Pruner for UnreachableGoals {
+    fn is_dead(&self, board: &Board) -> bool {
+        let particles = forms(board);
+        let annihilates = particles
+            .iter()
+            .enumerate()
+            .any(|(i, a)| particles[i + 1..].iter().any(|b| a.annihilates(b)));
+        if annihilates {
+            return false;
+        }
+
+        board.goals().iter().any(|&goal| {
+            matches!(board.particles()[*goal], Particle::Empty(_))
+                && !Self::can_stop_on(board, *goal)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::DefaultKey;
+
+    use crate::container::NucleusParticle;
+    use crate::obstacle::{block::Block, hole::Hole};
+    use crate::particle::{Electron, Neutron, Tau};
+    use crate::property::Antiness;
+    use crate::solver::Solver;
+
+    use super::*;
+
+    #[test]
+    fn dead_boards() {
+        let mut board = Board::new(4, 4, vec![Ix2(1, 1).into(), Ix2(3, 3).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 0));
+        assert!(TooFewParticles.is_dead(&board));
+        board.add_particle(Neutron::default(), Ix2(0, 3));
+        assert!(!TooFewParticles.is_dead(&board));
+
+        assert!(!UnreachableGoals.is_dead(&board));
+        board.add_obstacle(Hole, Ix2(2, 1));
+        board.add_obstacle(Block, Ix2(1, 0));
+        assert!(!UnreachableGoals.is_dead(&board));
+        board.add_obstacle(Hole, Ix2(1, 2));
+        assert!(UnreachableGoals.is_dead(&board));
+
+        board.add_particle(Electron::default(), Ix2(3, 0));
+        board.add_particle(Electron::new(Antiness::Anti), Ix2(2, 0));
+        assert!(!UnreachableGoals.is_dead(&board));
+    }
+
+    #[test]
+    fn decay_opens_goals() {
+        let mut board = Board::new(7, 5, vec![Ix2(6, 2).into()].into());
+        for pos in [Ix2(5, 2), Ix2(6, 1), Ix2(6, 3)] {
+            board.add_obstacle(Hole, pos);
+        }
+        board.add_particle(Electron::default(), Ix2(4, 0));
+        board.add_particle(Tau::new(Antiness::Anti), Ix2(4, 4));
+        board.add_particle(Neutron::default(), Ix2(0, 2));
+        assert!(UnreachableGoals.is_dead(&board));
+
+        board.set_lifetime(Ix2(4, 4), 1);
+        assert!(!UnreachableGoals.is_dead(&board));
+        let expected = Solver::<DefaultKey>::new(board.clone())
+            .find_solution()
+            .unwrap();
+        let solution = Solver::<DefaultKey>::new(board)
+            .with_pruner(UnreachableGoals)
+            .find_solution()
+            .unwrap();
+        assert_eq!(solution.depth(), expected.depth());
+    }
+
+    #[test]
+    fn nucleus_stops_in_front_of_hole() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 1));
+        board.add_particle(Neutron::default(), Ix2(0, 2));
+        board.add_container(
+            NucleusParticle::new(
+                Antiness::Ordinary,
+                vec![].into(),
+                vec![Ix2(0, 1).into(), Ix2(0, 2).into()].into(),
+            )
+            .into(),
+        );
+        board.add_obstacle(Block, Ix2(3, 1));
+        board.add_obstacle(Hole, Ix2(3, 2));
+        board.add_obstacle(Hole, Ix2(2, 3));
+
+        assert!(!UnreachableGoals.is_dead(&board));
+        let solution = Solver::<DefaultKey>::new(board)
+            .with_pruner(UnreachableGoals)
+            .find_solution()
+            .unwrap();
+        assert_eq!(solution.depth(), 1);
+    }
+
+    #[test]
+    fn pruning_keeps_solutions() {
+        let mut board = Board::new(5, 5, vec![Ix2(2, 2).into()].into());
+        board.add_particle(Neutron::default(), Ix2(0, 2));
+        board.add_obstacle(Block, Ix2(3, 2));
+        board.add_obstacle(Hole, Ix2(0, 0));
+
+        let mut solver: Solver<DefaultKey> = Solver::new(board.clone());
+        let expected = solver.find_solution().unwrap();
+
+        let mut pruned: Solver<DefaultKey> = Solver::new(board)
+            .with_pruner(TooFewParticles)
+            .with_pruner(UnreachableGoals);
+        let solution = pruned.find_solution().unwrap();
+        assert_eq!(solution.depth(), expected.depth());
+        assert!(solution.explored() < expected.explored());
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::board::Board;
+use crate::movement::Move;
+
+/// A solved path from the root board, independent of how the search stored it.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Solution {
+    boards: Vec<Board>,
+    moves: Vec<Move>,
+    explored: usize,
+}
+
+impl Solution {
+    /// Builds a solution from the boards along a path, root first.
+    pub fn from_boards(boards: Vec<Board>, explored: usize) -> Self {
+        let moves = boards
+            .windows(2)
+            .map(|pair| {
+                pair[0]
+                    .move_to(&pair[1])
+                    .expect("boards are one move apart")
+            })
+            .collect();
+        Self {
+            boards,
+            moves,
+            explored,
+        }
+    }
+
+    /// Builds a solution by making `moves` one after another on `root`.
+    pub fn from_moves(root: Board, moves: Vec<Move>, explored: usize) -> Self {
+        let mut boards = vec![root];
+        for m in &moves {
+            boards.push(boards.last().unwrap().applied(m));
+        }
+        Self {
+            boards,
+            moves,
+            explored,
+        }
+    }
+
+    /// The root board, every board in between and the solved board.
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    /// The moves between consecutive [`Solution::boards`].
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    pub fn depth(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Number of boards the search looked at to find the solution.
+    pub fn explored(&self) -> usize {
+        self.explored
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (step, m) in self.moves.iter().enumerate() {
+            writeln!(f, "{}. {m}", step + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -1,22 +1,48 @@
 use slotmap::{Key, SlotMap};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
-use std::fmt::{self, Debug, Display, Formatter};
+use std::fmt::{self, Display, Formatter};
 use std::ops::Deref;
 
 pub type TreeMap<K, T> = SlotMap<K, Node<K, T>>;
 
-#[derive(Debug, Clone)]
-pub struct KeyError<'a, K: Key, V>(&'a SlotMap<K, V>, K);
+/// Why a tree operation on a key failed.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum KeyError<K: Key> {
+    /// The key is not in the tree, for example because its node was removed.
+    Missing(K),
+    /// [`Tree::connect`] was asked for an edge whose child is the parent
+    /// itself or one of its ancestors, which would close a cycle.
+    Cycle { parent: K, child: K },
+}
 
-impl<'a, K: Key, V: Debug> Error for KeyError<'a, K, V> {}
+impl<K: Key> KeyError<K> {
+    /// The missing key, or the child of the refused edge.
+    pub fn key(&self) -> K {
+        match *self {
+            Self::Missing(key) => key,
+            Self::Cycle { child, .. } => child,
+        }
+    }
+}
+
+impl<K: Key> Error for KeyError<K> {}
 
-impl<'a, K: Key, V> Display for KeyError<'a, K, V> {
+impl<K: Key> Display for KeyError<K> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "key does not exists in tree")
+        match self {
+            Self::Missing(key) => write!(f, "key {:?} does not exist in tree", key.data()),
+            Self::Cycle { parent, child } => write!(
+                f,
+                "connecting {:?} to {:?} would create a cycle",
+                parent.data(),
+                child.data()
+            ),
+        }
     }
 }
 
-pub type KeyResult<'a, T, K, V> = Result<V, KeyError<'a, K, Node<K, T>>>;
+pub type KeyResult<K, V> = Result<V, KeyError<K>>;
 
 #[derive(Debug)]
 pub struct Tree<K: Key, T> {
@@ -39,6 +65,8 @@ impl<K: Key, T> Tree<K, T> {
         let root = nodes.insert(Node {
             value,
             parents: vec![],
+            children: vec![],
+            depth: 0,
         });
         Self { nodes, root }
     }
@@ -47,38 +75,391 @@ impl<K: Key, T> Tree<K, T> {
         self.root
     }
 
-    pub fn get_value(&self, key: K) -> KeyResult<T, K, &T> {
-        Ok(&self.get(key).ok_or(KeyError(&self.nodes, key))?.value)
+    pub fn get_value(&self, key: K) -> KeyResult<K, &T> {
+        Ok(&self.get(key).ok_or(KeyError::Missing(key))?.value)
     }
 
-    pub fn parents_of(&self, key: K) -> KeyResult<T, K, &Vec<K>> {
-        Ok(&self
-            .nodes
-            .get(key)
-            .ok_or(KeyError(&self.nodes, key))?
-            .parents)
+    pub fn parents_of(&self, key: K) -> KeyResult<K, &Vec<K>> {
+        Ok(&self.nodes.get(key).ok_or(KeyError::Missing(key))?.parents)
     }
 
-    pub fn insert_child_of(&mut self, parent: K, value: T) -> KeyResult<T, K, K> {
-        if !self.nodes.contains_key(parent) {
-            return Err(KeyError(&self.nodes, parent));
-        }
-        Ok(self.nodes.insert(Node {
+    pub fn children_of(&self, key: K) -> KeyResult<K, &Vec<K>> {
+        Ok(&self.nodes.get(key).ok_or(KeyError::Missing(key))?.children)
+    }
+
+    /// Length of the shortest path from the root to `key`.
+    pub fn depth_of(&self, key: K) -> KeyResult<K, usize> {
+        Ok(self.nodes.get(key).ok_or(KeyError::Missing(key))?.depth)
+    }
+
+    pub fn insert_child_of(&mut self, parent: K, value: T) -> KeyResult<K, K> {
+        let Some(parent_node) = self.nodes.get(parent) else {
+            return Err(KeyError::Missing(parent));
+        };
+        let depth = parent_node.depth + 1;
+        let child = self.nodes.insert(Node {
             value,
             parents: vec![parent],
-        }))
+            children: vec![],
+            depth,
+        });
+        self.nodes[parent].children.push(child);
+        Ok(child)
     }
 
-    pub fn connect(&mut self, parent: K, child: K) -> KeyResult<T, K, ()> {
+    /// Adds `parent` as another parent of `child`. Edges that would make
+    /// `child` its own ancestor are refused, which takes a walk over the
+    /// descendants of `child`.
+    pub fn connect(&mut self, parent: K, child: K) -> KeyResult<K, ()> {
+        if self.is_ancestor_of(child, parent)? || parent == child {
+            return Err(KeyError::Cycle { parent, child });
+        }
+        self.connect_unchecked(parent, child)
+    }
+
+    /// [`Self::connect`] without looking for a cycle, for callers that know
+    /// the edge cannot close one, such as an edge one layer deeper or to a
+    /// node without children.
+    pub(crate) fn connect_unchecked(&mut self, parent: K, child: K) -> KeyResult<K, ()> {
         if !self.nodes.contains_key(parent) {
-            return Err(KeyError(&self.nodes, parent));
+            return Err(KeyError::Missing(parent));
         }
         if !self.nodes.contains_key(child) {
-            return Err(KeyError(&self.nodes, child));
+            return Err(KeyError::Missing(child));
+        }
+        self.nodes[child].parents.push(parent);
+        self.nodes[parent].children.push(child);
+
+        let mut queue = VecDeque::from([(child, self.nodes[parent].depth + 1)]);
+        while let Some((key, depth)) = queue.pop_front() {
+            if depth >= self.nodes[key].depth {
+                continue;
+            }
+            self.nodes[key].depth = depth;
+            queue.extend(
+                self.nodes[key]
+                    .children
+                    .iter()
+                    .map(|&child| (child, depth + 1)),
+            );
         }
-        self.nodes.get_mut(child).unwrap().parents.push(parent);
         Ok(())
     }
+
+    /// Removes `key` and every descendant that is only reachable through it,
+    /// and returns their values. Descendants with another parent are kept,
+    /// with their depths updated. Removing the root removes everything below
+    /// it but keeps the root itself.
+    pub fn remove_subtree(&mut self, key: K) -> KeyResult<K, Vec<T>> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+
+        let mut removed = HashSet::from([key]);
+        for node in self.descendant_order(key).into_iter().skip(1) {
+            if self.nodes[node]
+                .parents
+                .iter()
+                .all(|parent| removed.contains(parent))
+            {
+                removed.insert(node);
+            }
+        }
+        if key == self.root {
+            removed.remove(&key);
+        }
+
+        let mut orphaned = Vec::new();
+        for &node in &removed {
+            for parent in self.nodes[node].parents.clone() {
+                if !removed.contains(&parent) {
+                    self.nodes[parent].children.retain(|child| child != &node);
+                }
+            }
+            for child in self.nodes[node].children.clone() {
+                if !removed.contains(&child) {
+                    self.nodes[child].parents.retain(|parent| parent != &node);
+                    orphaned.push(child);
+                }
+            }
+        }
+        let values = removed
+            .into_iter()
+            .map(|node| self.nodes.remove(node).unwrap().value)
+            .collect();
+
+        for start in orphaned {
+            for node in self.descendant_order(start) {
+                let depth = self.nodes[node]
+                    .parents
+                    .iter()
+                    .map(|&parent| self.nodes[parent].depth + 1)
+                    .min()
+                    .unwrap_or(0);
+                self.nodes[node].depth = depth;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Nodes reachable from `key` along `children`, breadth first and each
+    /// once, starting with `key`.
+    pub fn bfs(&self, key: K) -> KeyResult<K, Bfs<'_, K, T>> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+        Ok(Bfs {
+            tree: self,
+            queue: VecDeque::from([key]),
+            seen: HashSet::from([key]),
+        })
+    }
+
+    /// Nodes reachable from `key` along `children`, depth first in pre-order
+    /// and each once, starting with `key`.
+    pub fn dfs(&self, key: K) -> KeyResult<K, Dfs<'_, K, T>> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+        Ok(Dfs {
+            tree: self,
+            stack: vec![key],
+            seen: HashSet::new(),
+        })
+    }
+
+    /// Every node `key` can be reached from, nearest first.
+    pub fn ancestors(&self, key: K) -> KeyResult<K, Vec<K>> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+
+        let mut ancestors = Vec::new();
+        let mut seen = HashSet::from([key]);
+        let mut queue = VecDeque::from([key]);
+        while let Some(node) = queue.pop_front() {
+            for &parent in &self.nodes[node].parents {
+                if seen.insert(parent) {
+                    ancestors.push(parent);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        Ok(ancestors)
+    }
+
+    /// Every node reachable from `key`, nearest first.
+    pub fn descendants(&self, key: K) -> KeyResult<K, Vec<K>> {
+        Ok(self.bfs(key)?.skip(1).collect())
+    }
+
+    /// Whether `descendant` can be reached from `ancestor` along `children`.
+    pub fn is_ancestor_of(&self, ancestor: K, descendant: K) -> KeyResult<K, bool> {
+        if !self.nodes.contains_key(descendant) {
+            return Err(KeyError::Missing(descendant));
+        }
+        Ok(ancestor != descendant && self.bfs(ancestor)?.any(|node| node == descendant))
+    }
+
+    /// Every path from the root to `key` along `parents`, root first. A parent
+    /// connected more than once is only followed once.
+    pub fn paths_to(&self, key: K) -> KeyResult<K, Paths<'_, K, T>> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+        Ok(Paths {
+            tree: self,
+            stack: vec![(key, 0)],
+        })
+    }
+
+    /// Number of paths [`Tree::paths_to`] yields, counted without listing
+    /// them. Saturates at `u128::MAX`.
+    pub fn count_paths_to(&self, key: K) -> KeyResult<K, u128> {
+        if !self.nodes.contains_key(key) {
+            return Err(KeyError::Missing(key));
+        }
+
+        let mut counts: HashMap<K, u128> = HashMap::new();
+        let mut stack = vec![key];
+        while let Some(&node) = stack.last() {
+            if counts.contains_key(&node) {
+                stack.pop();
+                continue;
+            }
+            let parents = self.distinct_parents(node);
+            let pending: Vec<K> = parents
+                .iter()
+                .filter(|parent| !counts.contains_key(parent))
+                .copied()
+                .collect();
+            if !pending.is_empty() {
+                stack.extend(pending);
+                continue;
+            }
+
+            let count = match parents.is_empty() {
+                true => 1,
+                false => parents
+                    .iter()
+                    .fold(0u128, |sum, parent| sum.saturating_add(counts[parent])),
+            };
+            counts.insert(node, count);
+            stack.pop();
+        }
+
+        Ok(counts[&key])
+    }
+
+    /// Every key in the tree, root first and each node after all of its
+    /// parents.
+    pub fn topological_order(&self) -> Vec<K> {
+        let mut order = vec![self.root];
+        let mut placed = HashSet::from([self.root]);
+        for start in self.nodes.keys() {
+            let mut stack = vec![start];
+            while let Some(&key) = stack.last() {
+                if placed.contains(&key) {
+                    stack.pop();
+                    continue;
+                }
+                let pending: Vec<K> = self.nodes[key]
+                    .parents
+                    .iter()
+                    .filter(|parent| !placed.contains(parent))
+                    .copied()
+                    .collect();
+                if !pending.is_empty() {
+                    stack.extend(pending);
+                    continue;
+                }
+                placed.insert(key);
+                order.push(key);
+                stack.pop();
+            }
+        }
+
+        order
+    }
+
+    /// `key` and its descendants, each after all of its parents that are
+    /// among them.
+    fn descendant_order(&self, key: K) -> Vec<K> {
+        let reachable: HashSet<K> = self.bfs(key).expect("Node not found").collect();
+        let mut pending: HashMap<K, usize> = reachable
+            .iter()
+            .map(|&node| {
+                let parents = self.nodes[node]
+                    .parents
+                    .iter()
+                    .filter(|parent| reachable.contains(parent))
+                    .count();
+                (node, parents)
+            })
+            .collect();
+        pending.insert(key, 0);
+
+        let mut order = Vec::new();
+        let mut ready = vec![key];
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for child in &self.nodes[node].children {
+                let count = pending.get_mut(child).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(*child);
+                }
+            }
+        }
+        order
+    }
+
+    fn distinct_parents(&self, key: K) -> Vec<K> {
+        let mut parents = Vec::new();
+        for &parent in &self.nodes[key].parents {
+            if !parents.contains(&parent) {
+                parents.push(parent);
+            }
+        }
+        parents
+    }
+}
+
+/// Iterator returned by [`Tree::paths_to`].
+pub struct Paths<'a, K: Key, T> {
+    tree: &'a Tree<K, T>,
+    stack: Vec<(K, usize)>,
+}
+
+impl<K: Key, T> Iterator for Paths<'_, K, T> {
+    type Item = Vec<K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&(node, next_parent)) = self.stack.last() {
+            let parents = self.tree.distinct_parents(node);
+            if parents.is_empty() {
+                let path = self.stack.iter().rev().map(|&(key, _)| key).collect();
+                self.stack.pop();
+                return Some(path);
+            }
+
+            match parents.get(next_parent) {
+                Some(&parent) => {
+                    self.stack.last_mut().unwrap().1 += 1;
+                    self.stack.push((parent, 0));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Iterator returned by [`Tree::bfs`].
+pub struct Bfs<'a, K: Key, T> {
+    tree: &'a Tree<K, T>,
+    queue: VecDeque<K>,
+    seen: HashSet<K>,
+}
+
+impl<K: Key, T> Iterator for Bfs<'_, K, T> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        for &child in &self.tree.nodes[node].children {
+            if self.seen.insert(child) {
+                self.queue.push_back(child);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// Iterator returned by [`Tree::dfs`].
+pub struct Dfs<'a, K: Key, T> {
+    tree: &'a Tree<K, T>,
+    stack: Vec<K>,
+    seen: HashSet<K>,
+}
+
+impl<K: Key, T> Iterator for Dfs<'_, K, T> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.seen.insert(node) {
+                continue;
+            }
+            self.stack
+                .extend(self.tree.nodes[node].children.iter().rev());
+            return Some(node);
+        }
+        None
+    }
 }
 
 impl<K, T> Default for Tree<K, T>
@@ -106,4 +487,137 @@ where
 pub struct Node<K: Key, T> {
     value: T,
     parents: Vec<K>,
+    children: Vec<K>,
+    /// Length of the shortest path from the root.
+    depth: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::DefaultKey;
+
+    use super::*;
+
+    #[test]
+    fn tree_paths() {
+        let mut tree: Tree<DefaultKey, &str> = Tree::new("root");
+        let root = tree.root();
+        let left = tree.insert_child_of(root, "left").unwrap();
+        let right = tree.insert_child_of(root, "right").unwrap();
+        let bottom = tree.insert_child_of(left, "bottom").unwrap();
+        tree.connect(right, bottom).unwrap();
+        tree.connect(right, bottom).unwrap();
+        let leaf = tree.insert_child_of(bottom, "leaf").unwrap();
+
+        let paths: Vec<_> = tree.paths_to(leaf).unwrap().collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec![root, left, bottom, leaf],
+                vec![root, right, bottom, leaf]
+            ]
+        );
+        assert_eq!(tree.count_paths_to(leaf).unwrap(), 2);
+        assert_eq!(
+            tree.paths_to(root).unwrap().collect::<Vec<_>>(),
+            vec![vec![root]]
+        );
+        assert_eq!(tree.count_paths_to(root).unwrap(), 1);
+    }
+
+    #[test]
+    fn tree_traversal() {
+        let mut tree: Tree<DefaultKey, &str> = Tree::new("root");
+        let root = tree.root();
+        let a = tree.insert_child_of(root, "a").unwrap();
+        let b = tree.insert_child_of(root, "b").unwrap();
+        let c = tree.insert_child_of(a, "c").unwrap();
+        let d = tree.insert_child_of(c, "d").unwrap();
+        let e = tree.insert_child_of(d, "e").unwrap();
+        assert_eq!(tree.depth_of(e).unwrap(), 4);
+
+        tree.connect(b, d).unwrap();
+        assert_eq!(tree.children_of(b).unwrap(), &vec![d]);
+        assert_eq!(tree.depth_of(d).unwrap(), 2);
+        assert_eq!(tree.depth_of(e).unwrap(), 3);
+
+        assert_eq!(
+            tree.bfs(root).unwrap().collect::<Vec<_>>(),
+            vec![root, a, b, c, d, e]
+        );
+        assert_eq!(
+            tree.dfs(root).unwrap().collect::<Vec<_>>(),
+            vec![root, a, c, d, e, b]
+        );
+        assert_eq!(tree.ancestors(e).unwrap(), vec![d, c, b, a, root]);
+        assert_eq!(tree.descendants(a).unwrap(), vec![c, d, e]);
+        assert!(tree.is_ancestor_of(b, e).unwrap());
+        assert!(!tree.is_ancestor_of(e, b).unwrap());
+        assert!(!tree.is_ancestor_of(a, a).unwrap());
+
+        let mut removed = tree.remove_subtree(a).unwrap();
+        removed.sort();
+        assert_eq!(removed, vec!["a", "c"]);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.children_of(root).unwrap(), &vec![b]);
+        assert_eq!(tree.parents_of(d).unwrap(), &vec![b]);
+        assert_eq!(tree.depth_of(e).unwrap(), 3);
+        assert!(tree.get_value(c).is_err());
+
+        tree.remove_subtree(root).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert!(tree.children_of(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn connect_rejects_cycles() {
+        let mut tree: Tree<DefaultKey, &str> = Tree::new("root");
+        let root = tree.root();
+        let a = tree.insert_child_of(root, "a").unwrap();
+        let b = tree.insert_child_of(a, "b").unwrap();
+
+        for (parent, child) in [(a, a), (b, a), (b, root)] {
+            let error = tree.connect(parent, child).unwrap_err();
+            assert_eq!(error, KeyError::Cycle { parent, child });
+            assert_eq!(error.key(), child);
+        }
+        assert_eq!(tree.parents_of(a).unwrap(), &vec![root]);
+        assert_eq!(tree.children_of(b).unwrap(), &Vec::<DefaultKey>::new());
+        assert_eq!(tree.topological_order(), vec![root, a, b]);
+
+        tree.connect(root, b).unwrap();
+        assert_eq!(tree.depth_of(b).unwrap(), 1);
+        assert_eq!(
+            KeyError::Cycle {
+                parent: b,
+                child: a
+            }
+            .to_string(),
+            format!(
+                "connecting {:?} to {:?} would create a cycle",
+                b.data(),
+                a.data()
+            )
+        );
+    }
+
+    #[test]
+    fn key_error() {
+        let mut tree: Tree<DefaultKey, &str> = Tree::new("root");
+        let child = tree.insert_child_of(tree.root(), "child").unwrap();
+        tree.remove_subtree(child).unwrap();
+
+        let error = tree.get_value(child).unwrap_err();
+        assert_eq!(error.key(), child);
+        assert_eq!(tree.parents_of(child), Err(error));
+        assert_eq!(tree.insert_child_of(child, "orphan"), Err(error));
+        assert_eq!(tree.connect(tree.root(), child), Err(error));
+        assert_eq!(
+            error.to_string(),
+            format!("key {:?} does not exist in tree", child.data())
+        );
+
+        let sent = std::thread::spawn(move || error).join().unwrap();
+        assert_eq!(sent, error);
+    }
 }